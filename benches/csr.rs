@@ -0,0 +1,64 @@
+// NOTE: the boxed adjacency lists against the CSR layout on the same million-edge random graph.
+// Run with `cargo bench --bench csr`
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant}
+};
+
+use adm::graphs::csr::CsrGraph;
+use adm::graphs::graph_like::GraphLike;
+use adm::graphs::weighted::WeightedGraph;
+
+const NVERT: usize = 200_000;
+const NEDGES: usize = 1_000_000;
+const RUNS: usize = 5;
+
+// NOTE: the xorshift generator of the unit tests, so every run benchmarks the same graph
+fn random_graph() -> WeightedGraph {
+    let mut state: u64 = 0x9b05688c2b3e6c1f;
+    let mut below = |n: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % n as u64) as usize
+    };
+    let mut graph = WeightedGraph::new(NVERT, true);
+    (0..NEDGES).for_each(|_| {
+        let (u, v, weight) = (below(NVERT), below(NVERT), 1 + below(99) as i32);
+        graph.insert_edge(u, v, weight);
+    });
+    graph
+}
+
+// NOTE: the fastest of `RUNS` runs, which is the one least disturbed by the rest of the machine
+fn fastest<T>(run: impl Fn() -> T) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        black_box(run());
+        start.elapsed()
+    }).min().expect("there is at least one run")
+}
+
+fn compare<T: PartialEq + fmt::Debug>(label: &str, lists: impl Fn() -> T, csr: impl Fn() -> T) {
+    assert!(lists() == csr(), "{} gives different results on the two layouts", label);
+    let (lists, csr) = (fastest(lists), fastest(csr));
+    println!("{:>12} {:>14.2?} {:>14.2?} {:>8.2}x", label, lists, csr, lists.as_secs_f64() / csr.as_secs_f64());
+}
+
+fn main() {
+    let graph = random_graph();
+    let csr = CsrGraph::from(&graph);
+    println!("{} vertices, {} edges, fastest of {} runs", NVERT, NEDGES, RUNS);
+    println!("{:>12} {:>14} {:>14} {:>9}", "", "boxed lists", "csr", "speedup");
+    compare("bfs", || graph.bfs_iter(0).collect::<Vec<_>>(), || csr.bfs_iter(0).collect::<Vec<_>>());
+    compare("dfs", || graph.dfs_iter(0).collect::<Vec<_>>(), || csr.dfs_iter(0).collect::<Vec<_>>());
+    compare("dijkstras", || {
+        let paths = graph.dijkstras(0).unwrap();
+        (0..NVERT).map(|v| paths.distance_to(v)).collect::<Vec<_>>()
+    }, || {
+        let paths = csr.dijkstras(0).unwrap();
+        (0..NVERT).map(|v| paths.distance_to(v)).collect::<Vec<_>>()
+    });
+    compare("tarjans_scc", || graph.tarjans_scc(), || csr.tarjans_scc());
+}
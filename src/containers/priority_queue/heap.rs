@@ -89,8 +89,12 @@ impl<T: std::fmt::Debug + PartialOrd + Copy> Heap<T> {
                 cur_idx = child_idx + 1;
             }
         }
-        self.0[cur_idx] = self.0[self.0.len()-1];
+        let last = self.0.len() - 1;
+        self.0[cur_idx] = self.0[last];
         self.0.pop();
+        // NOTE: the hole may have sunk into a different subtree than the one the last element
+        // came from, so the moved element can be smaller than its new parent
+        if cur_idx < self.0.len() { self.bubble(cur_idx); }
     }
 }
 
@@ -139,6 +143,7 @@ mod test {
         assert_eq!(heap.pop(), None);
     }
 
+    #[test]
     pub fn large() { 
         let mut heap = Heap::new();
         heap.insert(1);
@@ -1,3 +1,20 @@
+pub mod weight;
 pub mod weighted;
+pub mod all_pairs;
+pub mod k_shortest;
+pub mod flow;
+pub mod assignment;
+pub mod arborescence;
+pub mod dag;
+pub mod tsp;
 pub mod unweighted;
+pub mod connectivity;
+pub mod graph_like;
+pub mod search;
+pub mod csr;
+pub mod matrix;
+pub mod formats;
  
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::weight::{self, Weight};
+use crate::graphs::weighted::{WeightedGraph, PathError, Path};
+
+#[derive(Debug)]
+pub struct AllPairsPaths<W: Weight = i32> {
+    // NOTE: `parents[u][v]` is the vertex preceding `v` on the shortest path from `u`
+    parents: Vec<Vec<Option<usize>>>,
+    distance: Vec<Vec<W>>,
+}
+
+// NOTE: the algorithms behind the all-pairs methods of `GraphLike`
+pub(crate) fn floyd_warshall<W: Weight, G: GraphLike<Weight = W>>(graph: &G) -> Result<AllPairsPaths<W>, PathError> {
+    let nvert = graph.vertices().len();
+    let mut distance: Vec<Vec<W>> = vec![vec![W::INFINITY; nvert]; nvert];
+    let mut parents: Vec<Vec<Option<usize>>> = vec![vec![None; nvert]; nvert];
+    for u in 0..nvert {
+        distance[u][u] = W::ZERO;
+        for (v, weight) in graph.weighted_neighbors(u) {
+            if u == v && weight < W::ZERO { return Err(PathError::NegativeCycle(vec![u])); }
+            if weight < distance[u][v] {
+                distance[u][v] = weight;
+                parents[u][v] = Some(u);
+            }
+        }
+    }
+
+    // NOTE: `beyond[i][j]` is set when a path from `i` to `j` weighs more than `W` can hold
+    let mut beyond: Vec<Vec<bool>> = vec![vec![false; nvert]; nvert];
+    for k in 0..nvert {
+        for i in 0..nvert {
+            if distance[i][k] == W::INFINITY { continue; }
+            for j in 0..nvert {
+                if distance[k][j] == W::INFINITY { continue; }
+                let candidate = match weight::relax(distance[i][k], distance[k][j]) {
+                    Ok(candidate) => candidate,
+                    Err(Ordering::Greater) => { beyond[i][j] = true; continue; },
+                    Err(_) if i != j => return Err(PathError::Overflow(i, j)),
+                    Err(_) => return Err(PathError::NegativeCycle(negative_cycle(&parents, i, k))),
+                };
+                // NOTE: stopping at the first negative diagonal keeps the cycle from driving the
+                // distances out of range and leaves the parent matrix describing it
+                if i == j && candidate < W::ZERO { return Err(PathError::NegativeCycle(negative_cycle(&parents, i, k))); }
+                if distance[i][j] > candidate {
+                    distance[i][j] = candidate;
+                    parents[i][j] = parents[k][j];
+                }
+            }
+        }
+    }
+
+    let out_of_range = (0..nvert).flat_map(|i| (0..nvert).map(move |j| (i, j)))
+        .find(|&(i, j)| beyond[i][j] && distance[i][j] == W::INFINITY);
+    match out_of_range {
+        Some((i, j)) => Err(PathError::Overflow(i, j)),
+        None => Ok(AllPairsPaths { parents, distance }),
+    }
+}
+
+// NOTE: the first negative diagonal `i` closes the walk `i ~> k ~> i` along two shortest paths
+// whose inner vertices all lie below `k`. Any cycle with a single vertex from `k` up would have
+// turned a diagonal negative earlier, so the negative cycle in the walk is the one through both
+// `i` and `k`: cutting out every loop that closes before returning to `i` leaves it
+fn negative_cycle(parents: &[Vec<Option<usize>>], i: usize, k: usize) -> Vec<usize> {
+    let path = |from: usize, to: usize| {
+        let mut path = vec![to];
+        let mut cur_vertex = to;
+        while cur_vertex != from {
+            cur_vertex = parents[from][cur_vertex].expect("shortest paths lead back to their start");
+            path.push(cur_vertex);
+        }
+        path.reverse();
+        path
+    };
+    let walk = path(i, k).into_iter().chain(path(k, i).into_iter().skip(1));
+    let mut position: Vec<Option<usize>> = vec![None; parents.len()];
+    let mut cycle: Vec<usize> = Vec::new();
+    for v in walk {
+        if v == i && !cycle.is_empty() { break; }
+        match position[v] {
+            Some(idx) => cycle.drain(idx + 1..).for_each(|u| position[u] = None),
+            None => {
+                position[v] = Some(cycle.len());
+                cycle.push(v);
+            },
+        }
+    }
+    cycle
+}
+
+pub(crate) fn johnsons<W: Weight, G: GraphLike<Weight = W>>(graph: &G) -> Result<AllPairsPaths<W>, PathError> {
+    let nvert = graph.vertices().len();
+    // NOTE: a virtual source with a zero edge to every vertex gives each vertex a potential
+    // `h` such that `weight + h[u] - h[v]` is never negative
+    let mut augmented = WeightedGraph::new(nvert + 1, true);
+    (0..nvert).for_each(|u| {
+        graph.weighted_neighbors(u).for_each(|(v, weight)| augmented.insert_edge(u, v, weight));
+        augmented.insert_edge(nvert, u, W::ZERO);
+    });
+    let potential = augmented.bellman_ford(nvert)?.distance;
+
+    let mut reweighted = WeightedGraph::new(nvert, true);
+    for u in 0..nvert {
+        for (v, weight) in graph.weighted_neighbors(u) {
+            let weight = reweight(weight, potential[u], potential[v]).ok_or(PathError::Overflow(u, v))?;
+            reweighted.insert_edge(u, v, weight);
+        }
+    }
+
+    // NOTE: `d` is never negative and `potential[v]` never positive, so only the last step can
+    // overflow, and then the distance is out of range just as `bellman_ford` would report
+    let mut distance = Vec::with_capacity(nvert);
+    let mut parents = Vec::with_capacity(nvert);
+    for u in 0..nvert {
+        let shortest_paths = reweighted.dijkstras(u)?;
+        let row = shortest_paths.distance.iter().enumerate().map(|(v, &d)| {
+            if d == W::INFINITY { return Ok(d); }
+            let d = d.checked_add(potential[v]).expect("`d` and `potential[v]` have opposite signs");
+            d.checked_sub(potential[u]).filter(|&d| d != W::INFINITY).ok_or(PathError::Overflow(u, v))
+        }).collect::<Result<Vec<W>, PathError>>()?;
+        distance.push(row);
+        parents.push(shortest_paths.parents);
+    }
+    Ok(AllPairsPaths { parents, distance })
+}
+
+// NOTE: `weight + from - to` for potentials that are never positive. Starting with the term of
+// opposite sign keeps the intermediate sum in range whenever the result is
+fn reweight<W: Weight>(weight: W, from: W, to: W) -> Option<W> {
+    if weight < W::ZERO {
+        weight.checked_sub(to)?.checked_add(from)
+    } else {
+        weight.checked_add(from)?.checked_sub(to)
+    }
+}
+
+impl<W: Weight> AllPairsPaths<W> {
+    pub fn distance(&self, u: usize, v: usize) -> Option<W> {
+        match self.distance[u][v] {
+            d if d == W::INFINITY => None,
+            d => Some(d),
+        }
+    }
+
+    pub fn path(&self, u: usize, v: usize) -> Option<Path<W>> {
+        let weight = self.distance(u, v)?;
+        let mut path = vec![v];
+        let mut cur_vertex = v;
+        while cur_vertex != u {
+            cur_vertex = self.parents[u][cur_vertex]?;
+            path.push(cur_vertex);
+        }
+        path.reverse();
+        Some(Path::new(path, weight))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    fn clrs() -> WeightedGraph {
+        let mut graph = WeightedGraph::new(5, true);
+        graph.insert_edge(0, 1, 3);
+        graph.insert_edge(0, 2, 8);
+        graph.insert_edge(0, 4, -4);
+        graph.insert_edge(1, 3, 1);
+        graph.insert_edge(1, 4, 7);
+        graph.insert_edge(2, 1, 4);
+        graph.insert_edge(3, 0, 2);
+        graph.insert_edge(3, 2, -5);
+        graph.insert_edge(4, 3, 6);
+        graph
+    }
+
+    #[test]
+    fn floyd_warshall() {
+        let expected = vec![
+            vec![0, 1, -3, 2, -4],
+            vec![3, 0, -4, 1, -1],
+            vec![7, 4, 0, 5, 3],
+            vec![2, -1, -5, 0, -2],
+            vec![8, 5, 1, 6, 0],
+        ];
+        let all_pairs = clrs().floyd_warshall().unwrap();
+        assert_eq!(all_pairs.distance, expected);
+        let path = all_pairs.path(0, 1).unwrap();
+        assert_eq!(path.path, vec![0, 4, 3, 2, 1]);
+        assert_eq!(path.weight, 1);
+        assert_eq!(all_pairs.path(2, 2).unwrap().path, vec![2]);
+    }
+
+    #[test]
+    fn johnsons() {
+        let graph = clrs();
+        let johnsons = graph.johnsons().unwrap();
+        let floyd_warshall = graph.floyd_warshall().unwrap();
+        assert_eq!(johnsons.distance, floyd_warshall.distance);
+        assert_eq!(johnsons.path(0, 1).unwrap().path, vec![0, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, 2);
+        let all_pairs = graph.johnsons().unwrap();
+        assert_eq!(all_pairs.distance(0, 1), Some(2));
+        assert_eq!(all_pairs.distance(1, 0), None);
+        assert!(all_pairs.path(0, 2).is_none());
+        assert!(graph.floyd_warshall().unwrap().path(2, 0).is_none());
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = clrs();
+        graph.insert_edge(2, 0, -10);
+        assert!(matches!(graph.floyd_warshall(), Err(PathError::NegativeCycle(_))));
+        assert!(matches!(graph.johnsons(), Err(PathError::NegativeCycle(_))));
+
+        // NOTE: the cycle weighs `i32::MIN + 1`, so going around it twice leaves the range
+        let mut graph = WeightedGraph::new(2, true);
+        graph.insert_edge(0, 1, i32::MIN + 1);
+        graph.insert_edge(1, 0, 0);
+        assert_eq!(graph.floyd_warshall().unwrap_err(), PathError::NegativeCycle(vec![1, 0]));
+        let mut graph = WeightedGraph::new(2, true);
+        graph.insert_edge(1, 1, -1);
+        assert_eq!(graph.floyd_warshall().unwrap_err(), PathError::NegativeCycle(vec![1]));
+    }
+
+    #[test]
+    fn negative_cycle_random() {
+        let mut rng = XorShift(0x0ddba11c0ffee123);
+        for round in 0..200 {
+            let nvert = 1 + rng.below(12);
+            let nedges = rng.below(nvert * 3);
+            let graph = rng.weighted_graph(nvert, nedges, true, -10..20);
+            let lightest = |u: usize, v: usize| graph.incident(u).filter(|&(w, _)| w == v).map(|(_, weight)| weight).min();
+            match graph.floyd_warshall() {
+                Err(PathError::NegativeCycle(cycle)) => {
+                    let mut vertices = cycle.clone();
+                    vertices.sort();
+                    vertices.dedup();
+                    assert_eq!(vertices.len(), cycle.len(), "round {}: {:?} is not simple", round, cycle);
+                    let weight: i32 = (0..cycle.len())
+                        .map(|idx| lightest(cycle[idx], cycle[(idx + 1) % cycle.len()]).expect("cycle edges exist"))
+                        .sum();
+                    assert!(weight < 0, "round {}: {:?} weighs {}", round, cycle, weight);
+                },
+                Ok(all_pairs) => (0..nvert).for_each(|v| {
+                    assert!(graph.bellman_ford(v).is_ok(), "round {}", round);
+                    assert_eq!(all_pairs.distance(v, v), Some(0));
+                }),
+                Err(error) => panic!("round {}: unexpected {:?}", round, error),
+            }
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        // NOTE: the potentials are 0, -5 and 0, so reweighting adds 5 to every edge into 1
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, -5);
+        graph.insert_edge(1, 2, i32::MAX - 10);
+        let johnsons = graph.johnsons().unwrap();
+        assert_eq!(johnsons.distance(0, 2), Some(i32::MAX - 15));
+        assert_eq!(johnsons.distance(1, 2), Some(i32::MAX - 10));
+        assert_eq!(graph.floyd_warshall().unwrap().distance(0, 2), Some(i32::MAX - 15));
+
+        graph.insert_edge(2, 1, i32::MAX - 1);
+        assert_eq!(graph.johnsons().unwrap_err(), PathError::Overflow(2, 1));
+
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX - 1);
+        graph.insert_edge(1, 2, 5);
+        assert_eq!(graph.johnsons().unwrap_err(), PathError::Overflow(1, 2));
+        assert_eq!(graph.floyd_warshall().unwrap_err(), PathError::Overflow(0, 2));
+        graph.insert_edge(0, 2, 3);
+        assert_eq!(graph.floyd_warshall().unwrap().distance(0, 2), Some(3));
+
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MIN + 1);
+        graph.insert_edge(1, 2, -5);
+        assert_eq!(graph.floyd_warshall().unwrap_err(), PathError::Overflow(0, 2));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0xdeadbeefcafef00d);
+        for _ in 0..30 {
+            let nvert = 2 + rng.below(25);
+            // NOTE: edges only point "forward" so negative weights cannot form a cycle
+            let mut graph = WeightedGraph::new(nvert, true);
+            (0..rng.below(nvert * 3)).for_each(|_| {
+                let (u, v) = (rng.below(nvert), rng.below(nvert));
+                if u < v { graph.insert_edge(u, v, rng.below(30) as i32 - 10); }
+            });
+            let floyd_warshall = graph.floyd_warshall().unwrap();
+            let johnsons = graph.johnsons().unwrap();
+            for u in 0..nvert {
+                let bellman_ford = graph.bellman_ford(u).unwrap();
+                for v in 0..nvert {
+                    let expected = match bellman_ford.distance[v] { i32::MAX => None, d => Some(d) };
+                    assert_eq!(floyd_warshall.distance(u, v), expected);
+                    assert_eq!(johnsons.distance(u, v), expected);
+                    if let Some(path) = johnsons.path(u, v) {
+                        assert_eq!(Some(path.weight), expected);
+                        assert_eq!((path.path[0], *path.path.last().unwrap()), (u, v));
+                    }
+                }
+            }
+        }
+    }
+}
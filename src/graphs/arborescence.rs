@@ -0,0 +1,253 @@
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::{WeightedGraph, SpanningTreeError};
+
+#[derive(Debug)]
+pub struct Arborescence<W: Weight = i32> {
+    root: usize,
+    parents: Vec<Option<usize>>,
+    edges: Vec<(usize, usize, W)>,
+    total_weight: W,
+}
+
+// NOTE: an arc `(from, points_to, weight, id)` where `id` indexes the arcs of the original graph
+#[derive(Debug, Clone, Copy)]
+struct Arc<W: Weight> {
+    from: usize,
+    points_to: usize,
+    weight: W,
+    id: usize,
+}
+
+impl<W: Weight> WeightedGraph<W> {
+    // NOTE: Chu-Liu/Edmonds. Every vertex takes its cheapest incoming arc; if those arcs form a
+    // cycle it is contracted into a single vertex (discounting the arcs entering it by the arc they
+    // would replace) and the contracted graph is solved recursively, O(VE) overall
+    pub fn min_arborescence(&self, root: usize) -> Result<Arborescence<W>, SpanningTreeError> {
+        let nvert = self.vcapacity();
+        let mut originals = Vec::new();
+        (0..nvert).for_each(|u| {
+            self.incident(u).for_each(|(v, weight)| originals.push((u, v, weight)));
+        });
+        let arcs = originals.iter().enumerate()
+            .map(|(id, &(from, points_to, weight))| Arc { from, points_to, weight, id })
+            .collect();
+
+        let chosen = contract(nvert, root, arcs, &originals)?;
+        let mut parents = vec![None; nvert];
+        let edges: Vec<(usize, usize, W)> = chosen.iter().map(|&id| originals[id]).collect();
+        edges.iter().for_each(|&(u, v, _)| parents[v] = Some(u));
+        let total_weight = edges.iter().try_fold(W::ZERO, |total, &(u, v, weight)| {
+            total.checked_add(weight).ok_or(SpanningTreeError::Overflow(u, v))
+        })?;
+        Ok(Arborescence { root, parents, edges, total_weight })
+    }
+}
+
+fn contract<W: Weight>(nvert: usize, root: usize, arcs: Vec<Arc<W>>, originals: &[(usize, usize, W)]) -> Result<Vec<usize>, SpanningTreeError> {
+    let mut cheapest: Vec<Option<Arc<W>>> = vec![None; nvert];
+    for &arc in arcs.iter().filter(|arc| arc.from != arc.points_to && arc.points_to != root) {
+        if cheapest[arc.points_to].is_none_or(|best| arc.weight < best.weight) {
+            cheapest[arc.points_to] = Some(arc);
+        }
+    }
+    if let Some(v) = (0..nvert).find(|&v| v != root && cheapest[v].is_none()) {
+        return Err(SpanningTreeError::Unreachable(v));
+    }
+
+    // NOTE: follow the cheapest arcs backwards from every vertex; a walk that runs into itself
+    // has found a cycle, and every vertex on it gets the same new (contracted) id
+    let mut component: Vec<Option<usize>> = vec![None; nvert];
+    let mut visited_by: Vec<Option<usize>> = vec![None; nvert];
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+    for start in 0..nvert {
+        let mut cur_vertex = start;
+        while cur_vertex != root && visited_by[cur_vertex].is_none() {
+            visited_by[cur_vertex] = Some(start);
+            cur_vertex = cheapest[cur_vertex].expect("non-root vertices have an incoming arc").from;
+        }
+        if cur_vertex != root && visited_by[cur_vertex] == Some(start) && component[cur_vertex].is_none() {
+            let mut cycle = vec![cur_vertex];
+            component[cur_vertex] = Some(cycles.len());
+            let mut on_cycle = cheapest[cur_vertex].expect("non-root vertices have an incoming arc").from;
+            while on_cycle != cur_vertex {
+                component[on_cycle] = Some(cycles.len());
+                cycle.push(on_cycle);
+                on_cycle = cheapest[on_cycle].expect("non-root vertices have an incoming arc").from;
+            }
+            cycles.push(cycle);
+        }
+    }
+
+    if cycles.is_empty() {
+        return Ok((0..nvert).filter_map(|v| cheapest[v].map(|arc| arc.id)).collect());
+    }
+
+    let mut ncomponents = cycles.len();
+    let component: Vec<usize> = component.into_iter().map(|c| c.unwrap_or_else(|| {
+        ncomponents += 1;
+        ncomponents - 1
+    })).collect();
+    let mut entering: Vec<Option<usize>> = vec![None; originals.len()];
+    let contracted = arcs.iter()
+        .filter(|arc| component[arc.from] != component[arc.points_to])
+        .map(|arc| {
+            // NOTE: remember which vertex the arc actually enters so the cycle can be broken there
+            entering[arc.id] = Some(arc.points_to);
+            let discount = if component[arc.points_to] < cycles.len() {
+                cheapest[arc.points_to].expect("non-root vertices have an incoming arc").weight
+            } else {
+                W::ZERO
+            };
+            let (u, v, _) = originals[arc.id];
+            let weight = arc.weight.checked_sub(discount).ok_or(SpanningTreeError::Overflow(u, v))?;
+            Ok(Arc { from: component[arc.from], points_to: component[arc.points_to], weight, id: arc.id })
+        })
+        .collect::<Result<Vec<Arc<W>>, SpanningTreeError>>()?;
+
+    let mut chosen = contract(ncomponents, component[root], contracted, originals).map_err(|err| match err {
+        SpanningTreeError::Unreachable(c) => {
+            SpanningTreeError::Unreachable((0..nvert).find(|&v| component[v] == c).expect("every component has a vertex"))
+        },
+        err => err,
+    })?;
+    let broken: Vec<usize> = chosen.iter()
+        .filter_map(|&id| entering[id])
+        .filter(|&v| component[v] < cycles.len())
+        .collect();
+    for cycle in &cycles {
+        cycle.iter()
+            .filter(|v| !broken.contains(v))
+            .for_each(|&v| chosen.push(cheapest[v].expect("non-root vertices have an incoming arc").id));
+    }
+    Ok(chosen)
+}
+
+impl<W: Weight> Arborescence<W> {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    // NOTE: tries every choice of incoming arc per vertex and keeps the cheapest acyclic one
+    fn brute_force(graph: &WeightedGraph, root: usize) -> Option<i32> {
+        let nvert = graph.vcapacity();
+        let mut incoming: Vec<Vec<(usize, i32)>> = vec![Vec::new(); nvert];
+        (0..nvert).for_each(|u| graph.incident(u).for_each(|(v, w)| if u != v { incoming[v].push((u, w)) }));
+        fn search(v: usize, root: usize, incoming: &[Vec<(usize, i32)>], parents: &mut Vec<usize>) -> Option<i32> {
+            if v == incoming.len() {
+                let reaches_root = (0..incoming.len()).all(|start| {
+                    let mut cur_vertex = start;
+                    (0..incoming.len()).for_each(|_| if cur_vertex != root { cur_vertex = parents[cur_vertex] });
+                    cur_vertex == root
+                });
+                return if reaches_root { Some(0) } else { None };
+            }
+            if v == root { return search(v + 1, root, incoming, parents); }
+            incoming[v].iter().filter_map(|&(u, w)| {
+                parents[v] = u;
+                search(v + 1, root, incoming, parents).map(|rest| rest + w)
+            }).min()
+        }
+        search(0, root, &incoming, &mut vec![root; nvert])
+    }
+
+    fn check(graph: &WeightedGraph, arborescence: &Arborescence) {
+        let nvert = graph.vcapacity();
+        assert_eq!(arborescence.edges().len(), nvert - 1);
+        assert!(arborescence.parent(arborescence.root()).is_none());
+        (0..nvert).for_each(|start| {
+            let mut cur_vertex = start;
+            (0..nvert).for_each(|_| if let Some(p) = arborescence.parent(cur_vertex) { cur_vertex = p });
+            assert_eq!(cur_vertex, arborescence.root());
+        });
+        arborescence.edges().iter().for_each(|&(u, v, w)| assert!(graph.incident(u).any(|edge| edge == (v, w))));
+    }
+
+    #[test]
+    fn basics() {
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(0, 2, 10);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(2, 3, 1);
+        graph.insert_edge(3, 1, 1);
+        let arborescence = graph.min_arborescence(0).unwrap();
+        check(&graph, &arborescence);
+        assert_eq!(arborescence.total_weight(), 12);
+
+        assert_eq!(graph.min_arborescence(1).unwrap_err(), SpanningTreeError::Unreachable(0));
+    }
+
+    #[test]
+    fn overflow() {
+        // NOTE: contracting the cycle 1 <-> 2 discounts 0 -> 2 by `i32::MIN`
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX);
+        graph.insert_edge(0, 2, i32::MAX);
+        graph.insert_edge(1, 2, i32::MIN);
+        graph.insert_edge(2, 1, i32::MAX - 1);
+        assert_eq!(graph.min_arborescence(0).unwrap_err(), SpanningTreeError::Overflow(0, 2));
+
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX);
+        graph.insert_edge(1, 2, 1);
+        assert_eq!(graph.min_arborescence(0).unwrap_err(), SpanningTreeError::Overflow(1, 2));
+        graph.insert_edge(0, 2, i32::MIN);
+        assert_eq!(graph.min_arborescence(0).unwrap().total_weight(), -1);
+    }
+
+    #[test]
+    fn nested_cycles() {
+        let mut graph = WeightedGraph::new(6, true);
+        graph.insert_edge(0, 1, 20);
+        graph.insert_edge(0, 4, 30);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(2, 1, 1);
+        graph.insert_edge(2, 3, 2);
+        graph.insert_edge(3, 4, 1);
+        graph.insert_edge(4, 5, 1);
+        graph.insert_edge(5, 3, 1);
+        graph.insert_edge(4, 2, 5);
+        let arborescence = graph.min_arborescence(0).unwrap();
+        check(&graph, &arborescence);
+        assert_eq!(Some(arborescence.total_weight()), brute_force(&graph, 0));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x0f1e2d3c4b5a6978);
+        for _ in 0..60 {
+            let nvert = 1 + rng.below(6);
+            let nedges = rng.below(nvert * 4);
+            let graph = rng.weighted_graph(nvert, nedges, true, -5..20);
+            let root = rng.below(nvert);
+            match graph.min_arborescence(root) {
+                Ok(arborescence) => {
+                    check(&graph, &arborescence);
+                    assert_eq!(Some(arborescence.total_weight()), brute_force(&graph, root));
+                },
+                Err(err) => {
+                    assert!(matches!(err, SpanningTreeError::Unreachable(_)));
+                    assert_eq!(brute_force(&graph, root), None);
+                },
+            }
+        }
+    }
+}
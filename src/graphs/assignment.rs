@@ -0,0 +1,166 @@
+#[derive(Debug)]
+pub struct Assignment {
+    // NOTE: `matching[row]` is the column assigned to `row`
+    matching: Vec<usize>,
+    cost: i64,
+}
+
+// NOTE: the Hungarian algorithm for a square cost matrix, O(n^3). Rows are added one at a time and
+// the dual potentials `row_potential`/`col_potential` are adjusted so that every matched cell has
+// zero reduced cost. Potentials and the total stay within n times the largest `i32` cost in size,
+// so the arithmetic is done in `i64`
+pub fn hungarian(costs: &[Vec<i32>]) -> Assignment {
+    let n = costs.len();
+    assert!(costs.iter().all(|row| row.len() == n), "`costs` must be a square matrix");
+    // NOTE: column `n` is a virtual column used as the root of each alternating search
+    let mut row_potential = vec![0i64; n + 1];
+    let mut col_potential = vec![0i64; n + 1];
+    let mut col_match: Vec<Option<usize>> = vec![None; n + 1];
+    let mut way = vec![n; n + 1];
+    for row in 0..n {
+        col_match[n] = Some(row);
+        let mut cur_col = n;
+        let mut min_slack = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+        while let Some(cur_row) = col_match[cur_col] {
+            used[cur_col] = true;
+            let mut delta = i64::MAX;
+            let mut next_col = n;
+            for col in (0..n).filter(|&col| !used[col]) {
+                let reduced = i64::from(costs[cur_row][col]) - row_potential[cur_row] - col_potential[col];
+                if reduced < min_slack[col] {
+                    min_slack[col] = reduced;
+                    way[col] = cur_col;
+                }
+                if min_slack[col] < delta {
+                    delta = min_slack[col];
+                    next_col = col;
+                }
+            }
+            for col in 0..=n {
+                if used[col] {
+                    if let Some(matched) = col_match[col] { row_potential[matched] += delta; }
+                    col_potential[col] -= delta;
+                } else {
+                    min_slack[col] -= delta;
+                }
+            }
+            cur_col = next_col;
+        }
+        // NOTE: flip the alternating path back to the virtual column
+        while cur_col != n {
+            let prev_col = way[cur_col];
+            col_match[cur_col] = col_match[prev_col];
+            cur_col = prev_col;
+        }
+    }
+
+    let mut matching = vec![0; n];
+    (0..n).for_each(|col| {
+        if let Some(row) = col_match[col] { matching[row] = col; }
+    });
+    let cost = (0..n).map(|row| i64::from(costs[row][matching[row]])).sum();
+    Assignment { matching, cost }
+}
+
+impl Assignment {
+    pub fn matching(&self) -> &[usize] {
+        &self.matching
+    }
+
+    pub fn cost(&self) -> i64 {
+        self.cost
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::flow::FlowNetwork;
+    use crate::graphs::test_utils::XorShift;
+
+    fn brute_force(costs: &[Vec<i32>]) -> i64 {
+        fn permute(costs: &[Vec<i32>], row: usize, used: &mut Vec<bool>) -> i64 {
+            if row == costs.len() { return 0; }
+            let mut best = i64::MAX;
+            for col in 0..costs.len() {
+                if used[col] { continue; }
+                used[col] = true;
+                best = best.min(i64::from(costs[row][col]) + permute(costs, row + 1, used));
+                used[col] = false;
+            }
+            best
+        }
+        permute(costs, 0, &mut vec![false; costs.len()])
+    }
+
+    #[test]
+    fn basics() {
+        let costs = vec![
+            vec![9, 2, 7, 8],
+            vec![6, 4, 3, 7],
+            vec![5, 8, 1, 8],
+            vec![7, 6, 9, 4],
+        ];
+        let assignment = hungarian(&costs);
+        assert_eq!(assignment.matching(), &[1, 0, 2, 3]);
+        assert_eq!(assignment.cost(), 13);
+
+        let assignment = hungarian(&[]);
+        assert_eq!((assignment.matching().len(), assignment.cost()), (0, 0));
+    }
+
+    #[test]
+    fn negative_costs() {
+        let costs = vec![
+            vec![-1, 4],
+            vec![3, -5],
+        ];
+        assert_eq!(hungarian(&costs).cost(), -6);
+    }
+
+    #[test]
+    fn large_costs() {
+        let costs = vec![
+            vec![i32::MAX, i32::MAX, i32::MIN],
+            vec![i32::MIN, i32::MAX, i32::MAX],
+            vec![i32::MAX, i32::MIN, i32::MAX],
+        ];
+        let assignment = hungarian(&costs);
+        assert_eq!(assignment.matching(), &[2, 0, 1]);
+        assert_eq!(assignment.cost(), 3 * i64::from(i32::MIN));
+        let costs = vec![vec![i32::MAX; 4]; 4];
+        assert_eq!(hungarian(&costs).cost(), brute_force(&costs));
+    }
+
+    #[test]
+    #[should_panic(expected = "square")]
+    fn not_square() {
+        hungarian(&[vec![1, 2]]);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x5555aaaa3333cccc);
+        for _ in 0..40 {
+            let n = 1 + rng.below(7);
+            let costs: Vec<Vec<i32>> = (0..n).map(|_| (0..n).map(|_| rng.below(50) as i32 - 10).collect()).collect();
+            let assignment = hungarian(&costs);
+            let mut cols = assignment.matching().to_vec();
+            cols.sort();
+            assert_eq!(cols, (0..n).collect::<Vec<_>>());
+            assert_eq!(assignment.cost(), brute_force(&costs));
+
+            // NOTE: the same problem as a min-cost flow from a source through rows and columns
+            let mut network = FlowNetwork::new(2 * n + 2);
+            let (source, sink) = (2 * n, 2 * n + 1);
+            (0..n).for_each(|row| {
+                network.insert_edge(source, row, 1);
+                network.insert_edge(n + row, sink, 1);
+                (0..n).for_each(|col| network.insert_costed_edge(row, n + col, 1, costs[row][col]));
+            });
+            let (min_cost_flow, cost) = network.min_cost_flow(source, sink).unwrap();
+            assert_eq!((min_cost_flow.value(), cost), (n as i64, assignment.cost()));
+        }
+    }
+}
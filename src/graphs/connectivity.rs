@@ -0,0 +1,415 @@
+use std::cmp::Reverse;
+
+use crate::graphs::graph_like::{GraphLike, BiconnectivityError};
+use crate::graphs::search::{OnDiscover, Control};
+use crate::graphs::unweighted::Graph;
+
+// NOTE: bookkeeping for Tarjan's algorithm. `low[v]` is the smallest DFS index reachable from the
+// subtree of `v` through at most one non-tree edge into a vertex still on the stack
+struct Tarjan<'a, G: GraphLike> {
+    graph: &'a G,
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    component: Vec<usize>,
+    next_index: usize,
+    ncomponents: usize,
+}
+
+// NOTE: the low points of a depth-first search forest, worked out after the search from the
+// `entry_time`/`parents` bookkeeping of `DepthFirstSearcher`. `low[v]` is the earliest entry time
+// reachable from the subtree of `v` with a single back edge; a child `u` with `low[u] >= entry[v]`
+// cannot get around `v`, so `v` separates it (and with `low[u] > entry[v]`, so does the edge `v - u`)
+struct LowPoints {
+    entry: Vec<usize>,
+    low: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    // NOTE: every vertex in discovery order, so parents come before their children
+    order: Vec<usize>,
+}
+
+// NOTE: the algorithms behind the connectivity methods of `GraphLike`
+pub(crate) fn tarjans_scc<G: GraphLike>(graph: &G) -> Vec<usize> {
+    let nvert = graph.vertices().len();
+    let mut tarjan = Tarjan {
+        graph,
+        index: vec![None; nvert],
+        low: vec![0; nvert],
+        on_stack: vec![false; nvert],
+        stack: Vec::new(),
+        component: vec![0; nvert],
+        next_index: 0,
+        ncomponents: 0,
+    };
+    (0..nvert).for_each(|v| if tarjan.index[v].is_none() { tarjan.search_from(v) });
+    // NOTE: Tarjan's algorithm completes the components in reverse topological order
+    let ncomponents = tarjan.ncomponents;
+    tarjan.component.iter().map(|&c| ncomponents - 1 - c).collect()
+}
+
+// NOTE: the vertex finishing last lies in a source component, which is exactly what a search
+// of the transposed graph from it can reach; repeat in decreasing finishing order
+pub(crate) fn kosarajus_scc<G: GraphLike>(graph: &G) -> Vec<usize> {
+    let nvert = graph.vertices().len();
+    let mut transposed: Vec<Vec<usize>> = vec![Vec::new(); nvert];
+    graph.vertices().for_each(|u| graph.neighbors(u).for_each(|v| transposed[v].push(u)));
+    let mut component: Vec<Option<usize>> = vec![None; nvert];
+    let mut ncomponents = 0;
+    for root in finishing_order(graph) {
+        if component[root].is_some() { continue; }
+        component[root] = Some(ncomponents);
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            transposed[v].iter().for_each(|&u| {
+                if component[u].is_none() {
+                    component[u] = Some(ncomponents);
+                    stack.push(u);
+                }
+            });
+        }
+        ncomponents += 1;
+    }
+    component.into_iter().map(|c| c.expect("every vertex is a root or reached from one")).collect()
+}
+
+pub(crate) fn condensation<G: GraphLike>(graph: &G) -> Graph {
+    let component = tarjans_scc(graph);
+    let ncomponents = component.iter().max().map_or(0, |&c| c + 1);
+    let mut edges: Vec<(usize, usize)> = graph.vertices()
+        .flat_map(|u| graph.neighbors(u).map(move |v| (u, v)))
+        .map(|(u, v)| (component[u], component[v]))
+        .filter(|(cu, cv)| cu != cv)
+        .collect();
+    edges.sort();
+    edges.dedup();
+    let mut condensed = Graph::new(ncomponents, true);
+    edges.into_iter().for_each(|(cu, cv)| condensed.insert_edge(cu, cv));
+    condensed
+}
+
+pub(crate) fn articulation_points<G: GraphLike>(graph: &G) -> Result<Vec<usize>, BiconnectivityError> {
+    let low_points = low_points(graph)?;
+    let mut articulation = vec![false; low_points.order.len()];
+    let mut root_children = vec![0; low_points.order.len()];
+    for &v in &low_points.order {
+        let Some(p) = low_points.parents[v] else { continue };
+        // NOTE: a DFS root separates the graph exactly when it has more than one subtree
+        if low_points.parents[p].is_none() {
+            root_children[p] += 1;
+            if root_children[p] > 1 { articulation[p] = true; }
+        } else if low_points.low[v] >= low_points.entry[p] {
+            articulation[p] = true;
+        }
+    }
+    Ok(graph.vertices().filter(|&v| articulation[v]).collect())
+}
+
+pub(crate) fn bridges<G: GraphLike>(graph: &G) -> Result<Vec<(usize, usize)>, BiconnectivityError> {
+    let low_points = low_points(graph)?;
+    let mut bridges: Vec<(usize, usize)> = low_points.order.iter()
+        .filter_map(|&v| low_points.parents[v].map(|p| (p, v)))
+        .filter(|&(p, v)| low_points.low[v] > low_points.entry[p])
+        .map(|(p, v)| (p.min(v), p.max(v)))
+        .collect();
+    bridges.sort();
+    Ok(bridges)
+}
+
+// NOTE: every tree edge `p - v` either starts a new block, when nothing below `v` gets around `p`,
+// or lies in the block of the tree edge above `p`. A back edge always lies in the block of the
+// tree edge into its lower end, so the tree edges already account for every vertex
+pub(crate) fn biconnected_components<G: GraphLike>(graph: &G) -> Result<Vec<Vec<usize>>, BiconnectivityError> {
+    let low_points = low_points(graph)?;
+    let mut block_of: Vec<Option<usize>> = vec![None; low_points.order.len()];
+    let mut blocks: Vec<Vec<usize>> = Vec::new();
+    for &v in &low_points.order {
+        let Some(p) = low_points.parents[v] else { continue };
+        let block = if low_points.low[v] >= low_points.entry[p] {
+            blocks.push(vec![p]);
+            blocks.len() - 1
+        } else {
+            block_of[p].expect("an edge reaching above `p` means `p` is not a root")
+        };
+        block_of[v] = Some(block);
+        blocks[block].push(v);
+    }
+    blocks.iter_mut().for_each(|block| block.sort());
+    blocks.sort();
+    Ok(blocks)
+}
+
+// NOTE: every vertex, ordered by decreasing DFS exit time over a search of the whole graph
+fn finishing_order<G: GraphLike>(graph: &G) -> Vec<usize> {
+    let mut dfs = graph.init_dfs();
+    graph.vertices().for_each(|v| if !dfs.is_discovered(v) { dfs.search_from(v, &mut ()) });
+    let mut order: Vec<usize> = graph.vertices().collect();
+    order.sort_by_key(|&v| Reverse(dfs.exit_time(v)));
+    order
+}
+
+fn low_points<G: GraphLike>(graph: &G) -> Result<LowPoints, BiconnectivityError> {
+    if graph.is_directed() { return Err(BiconnectivityError::Directed); }
+    let mut dfs = graph.init_dfs();
+    let mut order = Vec::with_capacity(graph.vertices().len());
+    for v in graph.vertices() {
+        if dfs.is_discovered(v) { continue; }
+        dfs.search_from(v, &mut OnDiscover(|v| {
+            order.push(v);
+            Control::Continue
+        }));
+    }
+    let entry: Vec<usize> = graph.vertices().map(|v| dfs.entry_time(v).expect("every vertex is searched")).collect();
+    let parents: Vec<Option<usize>> = graph.vertices().map(|v| dfs.parent(v)).collect();
+
+    // NOTE: children are discovered after their parents, so in reverse discovery order every
+    // subtree is done before its low point is passed up. Undirected searches have no cross edges,
+    // so any earlier neighbor is an ancestor; only one copy of the tree edge to the parent is
+    // skipped, so a parallel edge to the parent counts as a back edge
+    let mut low = entry.clone();
+    for &v in order.iter().rev() {
+        let mut skipped_parent = false;
+        for u in graph.neighbors(v) {
+            if Some(u) == parents[v] && !skipped_parent {
+                skipped_parent = true;
+                continue;
+            }
+            low[v] = low[v].min(entry[u]);
+        }
+        if let Some(p) = parents[v] { low[p] = low[p].min(low[v]); }
+    }
+    Ok(LowPoints { entry, low, parents, order })
+}
+
+impl<'a, G: GraphLike> Tarjan<'a, G> {
+    // NOTE: the recursion of the textbook version made explicit, so long paths cannot overflow the
+    // call stack. Each frame is a vertex along with the neighbors it has left to look at
+    fn search_from(&mut self, root: usize) {
+        let graph = self.graph;
+        let mut frames: Vec<(usize, G::Neighbors<'a>)> = Vec::new();
+        self.enter(root);
+        frames.push((root, graph.neighbors(root)));
+        while let Some((v, neighbors)) = frames.last_mut() {
+            let (v, next) = (*v, neighbors.next());
+            match next {
+                Some(u) => match self.index[u] {
+                    None => {
+                        self.enter(u);
+                        frames.push((u, graph.neighbors(u)));
+                    },
+                    Some(index) if self.on_stack[u] => self.low[v] = self.low[v].min(index),
+                    Some(_) => {},
+                },
+                None => {
+                    frames.pop();
+                    self.finish(v);
+                    if let Some(&(parent, _)) = frames.last() { self.low[parent] = self.low[parent].min(self.low[v]); }
+                },
+            }
+        }
+    }
+
+    fn enter(&mut self, v: usize) {
+        self.index[v] = Some(self.next_index);
+        self.low[v] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+    }
+
+    // NOTE: nothing in the subtree of `v` reaches above it, so `v` roots a component made of
+    // everything pushed since
+    fn finish(&mut self, v: usize) {
+        if Some(self.low[v]) != self.index[v] { return; }
+        while let Some(u) = self.stack.pop() {
+            self.on_stack[u] = false;
+            self.component[u] = self.ncomponents;
+            if u == v { break; }
+        }
+        self.ncomponents += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::csr::CsrGraph;
+    use crate::graphs::test_utils::XorShift;
+
+    // NOTE: relabels components by first appearance so different numberings can be compared
+    fn normalize(component: &[usize]) -> Vec<usize> {
+        let mut relabel: Vec<Option<usize>> = vec![None; component.len()];
+        let mut next = 0;
+        component.iter().map(|&c| {
+            *relabel[c].get_or_insert_with(|| {
+                next += 1;
+                next - 1
+            })
+        }).collect()
+    }
+
+    fn reaches(graph: &Graph, start: usize) -> Vec<bool> {
+        let mut seen = vec![false; graph.vcapacity()];
+        let mut stack = vec![start];
+        seen[start] = true;
+        while let Some(v) = stack.pop() {
+            graph.incident(v).for_each(|u| if !seen[u] {
+                seen[u] = true;
+                stack.push(u);
+            });
+        }
+        seen
+    }
+
+    #[test]
+    fn scc() {
+        // NOTE: the example from CLRS 22.5
+        let mut graph = Graph::new(8, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(1, 4);
+        graph.insert_edge(1, 5);
+        graph.insert_edge(2, 3);
+        graph.insert_edge(2, 6);
+        graph.insert_edge(3, 2);
+        graph.insert_edge(3, 7);
+        graph.insert_edge(4, 0);
+        graph.insert_edge(4, 5);
+        graph.insert_edge(5, 6);
+        graph.insert_edge(6, 5);
+        graph.insert_edge(6, 7);
+        graph.insert_edge(7, 7);
+        let expected = vec![0, 0, 1, 1, 0, 2, 2, 3];
+        assert_eq!(graph.tarjans_scc(), expected);
+        assert_eq!(graph.kosarajus_scc(), expected);
+
+        let condensed = graph.condensation();
+        assert_eq!(condensed.vcapacity(), 4);
+        let mut edges: Vec<(usize, usize)> = (0..4).flat_map(|u| condensed.incident(u).map(move |v| (u, v))).collect();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+        assert!(condensed.topological_sort().is_ok());
+    }
+
+    fn without(graph: &Graph, skip: impl Fn(usize, usize) -> bool) -> Graph {
+        let mut pruned = Graph::new(graph.vcapacity(), false);
+        (0..graph.vcapacity()).for_each(|u| graph.incident(u).for_each(|v| {
+            if u <= v && !skip(u, v) { pruned.insert_edge(u, v); }
+        }));
+        pruned
+    }
+
+    #[test]
+    fn cut_vertices_and_bridges() {
+        // NOTE: two triangles 0-1-2 and 3-4-5 joined by the bridge 2 - 3, plus the pendant edge 5 - 6
+        let mut graph = Graph::new(8, false);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 0);
+        graph.insert_edge(2, 3);
+        graph.insert_edge(3, 4);
+        graph.insert_edge(4, 5);
+        graph.insert_edge(5, 3);
+        graph.insert_edge(5, 6);
+        assert_eq!(graph.articulation_points().unwrap(), vec![2, 3, 5]);
+        assert_eq!(graph.bridges().unwrap(), vec![(2, 3), (5, 6)]);
+        assert_eq!(graph.biconnected_components().unwrap(), vec![vec![0, 1, 2], vec![2, 3], vec![3, 4, 5], vec![5, 6]]);
+
+        // NOTE: doubling the bridge makes it a 2-connected piece of its own
+        graph.insert_edge(3, 2);
+        assert_eq!(graph.articulation_points().unwrap(), vec![2, 3, 5]);
+        assert_eq!(graph.bridges().unwrap(), vec![(5, 6)]);
+    }
+
+    #[test]
+    fn cut_vertices_long_chain() {
+        let nvert = 200_000;
+        let graph = CsrGraph::from_edges(nvert, false, &(1..nvert).map(|v| (v - 1, v, 1)).collect::<Vec<_>>());
+        assert_eq!(graph.bridges().unwrap(), (1..nvert).map(|v| (v - 1, v)).collect::<Vec<_>>());
+        assert_eq!(graph.articulation_points().unwrap(), (1..nvert - 1).collect::<Vec<_>>());
+        assert_eq!(graph.biconnected_components().unwrap().len(), nvert - 1);
+
+        let graph = CsrGraph::from_edges(nvert, false, &(0..nvert).map(|v| (v, (v + 1) % nvert, 1)).collect::<Vec<_>>());
+        assert!(graph.bridges().unwrap().is_empty() && graph.articulation_points().unwrap().is_empty());
+        assert_eq!(graph.biconnected_components().unwrap(), vec![(0..nvert).collect::<Vec<_>>()]);
+    }
+
+    #[test]
+    fn cut_vertices_directed() {
+        let graph = Graph::new(2, true);
+        assert_eq!(graph.articulation_points().unwrap_err(), BiconnectivityError::Directed);
+        assert_eq!(graph.bridges().unwrap_err(), BiconnectivityError::Directed);
+        assert_eq!(graph.biconnected_components().unwrap_err(), BiconnectivityError::Directed);
+    }
+
+    #[test]
+    fn cut_vertices_random() {
+        let mut rng = XorShift(0x2718281828459045);
+        for _ in 0..50 {
+            let nvert = 1 + rng.below(15);
+            let mut graph = Graph::new(nvert, false);
+            (0..rng.below(nvert * 2)).for_each(|_| graph.insert_edge(rng.below(nvert), rng.below(nvert)));
+            let ncomponents = graph.connected_components();
+
+            // NOTE: cutting a vertex off leaves it isolated, which is one extra component on its own
+            let expected: Vec<usize> = (0..nvert)
+                .filter(|&v| graph.incident(v).any(|u| u != v))
+                .filter(|&v| without(&graph, |a, b| a == v || b == v).connected_components() > ncomponents + 1)
+                .collect();
+            let articulation_points = graph.articulation_points().unwrap();
+            assert_eq!(articulation_points, expected);
+
+            let mut expected: Vec<(usize, usize)> = (0..nvert)
+                .flat_map(|u| graph.incident(u).filter(move |&v| u < v).map(move |v| (u, v)))
+                .filter(|&(u, v)| graph.incident(u).filter(|&w| w == v).count() == 1)
+                .filter(|&(u, v)| without(&graph, |a, b| (a, b) == (u, v)).connected_components() > ncomponents)
+                .collect();
+            expected.sort();
+            expected.dedup();
+            assert_eq!(graph.bridges().unwrap(), expected);
+
+            // NOTE: exactly the articulation points are shared between blocks, and in each connected
+            // piece the blocks and cut vertices form a tree
+            let blocks = graph.biconnected_components().unwrap();
+            let membership: Vec<usize> = (0..nvert).map(|v| blocks.iter().filter(|block| block.contains(&v)).count()).collect();
+            assert_eq!((0..nvert).filter(|&v| membership[v] > 1).collect::<Vec<_>>(), articulation_points);
+            let non_trivial = (0..nvert).filter(|&v| graph.incident(v).any(|u| u != v)).count();
+            let trivial_components = ncomponents - (nvert - non_trivial);
+            let shared: usize = articulation_points.iter().map(|&v| membership[v] - 1).sum();
+            assert_eq!(blocks.len(), trivial_components + shared);
+        }
+    }
+
+    #[test]
+    fn scc_long_chain() {
+        let nvert = 200_000;
+        let mut graph = Graph::new(nvert, true);
+        (1..nvert).for_each(|v| graph.insert_edge(v - 1, v));
+        assert_eq!(graph.tarjans_scc(), (0..nvert).collect::<Vec<_>>());
+        graph.insert_edge(nvert - 1, 0);
+        assert!(graph.tarjans_scc().iter().all(|&c| c == 0));
+        assert!(graph.kosarajus_scc().iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x3141592653589793);
+        for _ in 0..50 {
+            let nvert = 1 + rng.below(30);
+            let mut graph = Graph::new(nvert, true);
+            (0..rng.below(nvert * 2)).for_each(|_| graph.insert_edge(rng.below(nvert), rng.below(nvert)));
+            let tarjan = graph.tarjans_scc();
+            let kosaraju = graph.kosarajus_scc();
+            assert_eq!(normalize(&tarjan), normalize(&kosaraju));
+
+            let reach: Vec<Vec<bool>> = (0..nvert).map(|v| reaches(&graph, v)).collect();
+            (0..nvert).for_each(|u| (0..nvert).for_each(|v| {
+                assert_eq!(tarjan[u] == tarjan[v], reach[u][v] && reach[v][u]);
+            }));
+            (0..nvert).for_each(|u| graph.incident(u).for_each(|v| {
+                assert!(tarjan[u] <= tarjan[v] && kosaraju[u] <= kosaraju[v]);
+            }));
+            assert!(graph.condensation().topological_sort().is_ok());
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    fmt,
+    iter,
+    slice,
+    ops::Range
+};
+
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::unweighted::Graph;
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::WeightedGraph;
+
+// NOTE: an immutable graph in compressed sparse row form. The neighbors of `v` are
+// `targets[offsets[v]..offsets[v + 1]]` with matching `weights`, so the whole adjacency structure
+// lives in three flat arrays instead of one box per edge
+#[derive(Debug, Clone)]
+pub struct CsrGraph<W: Weight = i32> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+    nedges: usize,
+    directed: bool,
+}
+
+impl<W: Weight> CsrGraph<W> {
+    // NOTE: each list keeps the order of `edges`. As in the list graphs, undirected edges are
+    // stored at both endpoints (so a self-loop appears twice in the list of its vertex)
+    pub fn from_edges(nvert: usize, directed: bool, edges: &[(usize, usize, W)]) -> Self {
+        assert!(edges.iter().all(|&(u, v, _)| u < nvert && v < nvert), "edge endpoints must be below `nvert`");
+        let mut offsets = vec![0; nvert + 1];
+        edges.iter().for_each(|&(u, v, _)| {
+            offsets[u + 1] += 1;
+            if !directed { offsets[v + 1] += 1; }
+        });
+        (0..nvert).for_each(|v| offsets[v + 1] += offsets[v]);
+
+        // NOTE: counting sort by source, `next[v]` being the next free slot in the list of `v`
+        let mut next = offsets.clone();
+        let mut targets = vec![0; offsets[nvert]];
+        let mut weights = vec![W::ZERO; offsets[nvert]];
+        let mut place = |u: usize, v: usize, weight: W| {
+            targets[next[u]] = v;
+            weights[next[u]] = weight;
+            next[u] += 1;
+        };
+        edges.iter().for_each(|&(u, v, weight)| {
+            place(u, v, weight);
+            if !directed { place(v, u, weight); }
+        });
+        CsrGraph { offsets, targets, weights, nedges: edges.len(), directed }
+    }
+
+    // NOTE: copies the adjacency lists as they are, so searches visit neighbors in the same order
+    // as on the original graph
+    fn from_graph_like<G: GraphLike<Weight = W>>(graph: &G) -> Self {
+        let mut offsets = Vec::with_capacity(graph.vertices().len() + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+        offsets.push(0);
+        graph.vertices().for_each(|u| {
+            graph.weighted_neighbors(u).for_each(|(v, weight)| {
+                targets.push(v);
+                weights.push(weight);
+            });
+            offsets.push(targets.len());
+        });
+        let directed = graph.is_directed();
+        let nedges = if directed { targets.len() } else { targets.len() / 2 };
+        CsrGraph { offsets, targets, weights, nedges, directed }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    // NOTE: an undirected edge counts once even though it is stored at both endpoints
+    pub fn edge_count(&self) -> usize {
+        self.nedges
+    }
+
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.neighbors(i).any(|v| v == j)
+    }
+
+    fn slots(&self, v: usize) -> Range<usize> {
+        self.offsets[v]..self.offsets[v + 1]
+    }
+}
+
+impl<W: Weight> From<&WeightedGraph<W>> for CsrGraph<W> {
+    fn from(graph: &WeightedGraph<W>) -> Self {
+        CsrGraph::from_graph_like(graph)
+    }
+}
+
+// NOTE: every edge of an unweighted graph gets weight 1
+impl From<&Graph> for CsrGraph {
+    fn from(graph: &Graph) -> Self {
+        CsrGraph::from_graph_like(graph)
+    }
+}
+
+impl<W: Weight> GraphLike for CsrGraph<W> {
+    type Weight = W;
+    type Neighbors<'a> = iter::Copied<slice::Iter<'a, usize>> where W: 'a;
+    type WeightedNeighbors<'a> = iter::Zip<iter::Copied<slice::Iter<'a, usize>>, iter::Copied<slice::Iter<'a, W>>> where W: 'a;
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.vertex_count()
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.targets[self.slots(v)].iter().copied()
+    }
+
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_> {
+        iter::zip(self.targets[self.slots(v)].iter().copied(), self.weights[self.slots(v)].iter().copied())
+    }
+}
+
+impl<W: Weight> fmt::Display for CsrGraph<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for u in self.vertices().filter(|&u| self.out_degree(u) > 0) {
+            write!(f, "{}: ", u)?;
+            self.weighted_neighbors(u).try_for_each(|(v, weight)| write!(f, "{}[w:{}] ", v, weight))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+    use crate::graphs::weighted::Path;
+
+    #[test]
+    fn from_edges() {
+        let graph = CsrGraph::from_edges(4, true, &[(0, 1, 5), (2, 3, 1), (0, 2, 2), (1, 1, 4)]);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (4, 4));
+        assert_eq!(graph.weighted_neighbors(0).collect::<Vec<_>>(), vec![(1, 5), (2, 2)]);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(graph.out_degree(3), 0);
+        assert!(graph.has_edge(2, 3) && !graph.has_edge(3, 2));
+        assert_eq!(format!("{}", graph), "0: 1[w:5] 2[w:2] \n1: 1[w:4] \n2: 3[w:1] \n");
+
+        let graph = CsrGraph::from_edges(3, false, &[(0, 1, 1), (1, 2, 1), (2, 2, 1)]);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![1, 2, 2]);
+        assert_eq!(CsrGraph::<i32>::from_edges(0, true, &[]).vertices(), 0..0);
+    }
+
+    #[test]
+    fn algorithms() {
+        let mut graph = Graph::new(6, true);
+        [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)].into_iter().for_each(|(u, v)| graph.insert_edge(u, v));
+        let csr = CsrGraph::from(&graph);
+        assert_eq!(csr.edge_count(), 6);
+        assert_eq!(csr.tarjans_scc(), graph.tarjans_scc());
+        assert_eq!(csr.kosarajus_scc(), graph.kosarajus_scc());
+        assert_eq!(csr.find_path(0, 4), Some(vec![0, 1, 2, 3, 4]));
+        assert_eq!(csr.connected_components(), 2);
+        assert!(csr.topological_sort().is_err());
+        let paths = csr.dijkstras(0).unwrap();
+        assert_eq!(paths.distance_to(4), Some(4));
+        assert_eq!(paths.distance_to(5), None);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x510e527fade682d1);
+        for directed in [false, true] {
+            for _ in 0..20 {
+                let nvert = 1 + rng.below(30);
+                let edges: Vec<(usize, usize, i32)> = (0..rng.below(nvert * 3))
+                    .map(|_| (rng.below(nvert), rng.below(nvert), rng.below(20) as i32))
+                    .collect();
+                let mut graph = WeightedGraph::new(nvert, directed);
+                edges.iter().for_each(|&(u, v, weight)| graph.insert_edge(u, v, weight));
+                let csr = CsrGraph::from(&graph);
+                assert_eq!(csr.edge_count(), graph.edge_count());
+                graph.vertices().for_each(|v| {
+                    assert_eq!(csr.weighted_neighbors(v).collect::<Vec<_>>(), graph.weighted_neighbors(v).collect::<Vec<_>>());
+                });
+                assert_eq!(csr.dfs_events(0).collect::<Vec<_>>(), graph.dfs_events(0).collect::<Vec<_>>());
+                assert_eq!(csr.dijkstras(0).unwrap().distance, graph.dijkstras(0).unwrap().distance);
+                assert_eq!(csr.bellman_ford(0).unwrap().distance, graph.bellman_ford(0).unwrap().distance);
+
+                // NOTE: building from the edge list gives the same lists, just in insertion order
+                let rebuilt = CsrGraph::from_edges(nvert, directed, &edges);
+                assert_eq!(rebuilt.edge_count(), graph.edge_count());
+                assert_eq!(rebuilt.dijkstras(0).unwrap().distance, graph.dijkstras(0).unwrap().distance);
+            }
+        }
+    }
+
+    #[test]
+    fn shortest_paths_random() {
+        let mut rng = XorShift(0x3c6ef372fe94f82b);
+        for _ in 0..20 {
+            let nvert = 1 + rng.below(15);
+            // NOTE: edges only point forward so negative weights cannot form a cycle
+            let mut dag = WeightedGraph::new(nvert, true);
+            (0..rng.below(nvert * 3)).for_each(|_| {
+                let (u, v) = (rng.below(nvert), rng.below(nvert));
+                if u < v { dag.insert_edge(u, v, rng.below(30) as i32 - 10); }
+            });
+            let csr = CsrGraph::from(&dag);
+            let start = rng.below(nvert);
+            assert_eq!(csr.dag_shortest_paths(start).unwrap().distance, dag.dag_shortest_paths(start).unwrap().distance);
+            assert_eq!(csr.dag_longest_paths(start).unwrap().distance, dag.dag_longest_paths(start).unwrap().distance);
+            let (critical, expected) = (csr.critical_path().unwrap(), dag.critical_path().unwrap());
+            assert_eq!((critical.length(), critical.critical_chain()), (expected.length(), expected.critical_chain()));
+            let (floyd_warshall, johnsons) = (csr.floyd_warshall().unwrap(), csr.johnsons().unwrap());
+            let expected = dag.floyd_warshall().unwrap();
+            (0..nvert).for_each(|u| (0..nvert).for_each(|v| {
+                assert_eq!(floyd_warshall.distance(u, v), expected.distance(u, v));
+                assert_eq!(johnsons.distance(u, v), expected.distance(u, v));
+            }));
+
+            let nedges = rng.below(nvert * 3);
+            let graph = rng.weighted_graph(nvert, nedges, false, 1..10);
+            let csr = CsrGraph::from(&graph);
+            let end = rng.below(nvert);
+            let weights = |paths: Vec<Path>| paths.iter().map(|path| path.weight()).collect::<Vec<_>>();
+            assert_eq!(weights(csr.k_shortest_paths(start, end, 5).unwrap()), weights(graph.k_shortest_paths(start, end, 5).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+use crate::graphs::graph_like::{GraphLike, TopologicalError};
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::ShortestPaths;
+
+#[derive(Debug, PartialEq)]
+pub enum DagError {
+    Undirected,
+    Cycle(Vec<usize>),
+    Overflow(usize, usize),
+}
+
+#[derive(Debug)]
+pub struct CriticalPath<W: Weight = i32> {
+    earliest: Vec<W>,
+    latest: Vec<W>,
+    length: W,
+    chain: Vec<usize>,
+}
+
+// NOTE: the DFS-based sort, O(V + E), so shortest and longest paths stay linear as well
+pub(crate) fn topological_order<G: GraphLike>(graph: &G) -> Result<Vec<usize>, DagError> {
+    graph.topological_sort().map_err(|err| match err {
+        TopologicalError::Undirected => DagError::Undirected,
+        TopologicalError::Cycle(cycle) => DagError::Cycle(cycle.vertices().to_vec()),
+    })
+}
+
+pub(crate) fn dag_shortest_paths<W: Weight, G: GraphLike<Weight = W>>(graph: &G, start: usize) -> Result<ShortestPaths<'_, W, G>, DagError> {
+    dag_paths(graph, start, |candidate, current| candidate < current)
+}
+
+pub(crate) fn dag_longest_paths<W: Weight, G: GraphLike<Weight = W>>(graph: &G, start: usize) -> Result<ShortestPaths<'_, W, G>, DagError> {
+    dag_paths(graph, start, |candidate, current| current == W::INFINITY || candidate > current)
+}
+
+// NOTE: relaxing edges in topological order settles every vertex in one pass, so negative
+// weights and longest paths come for free
+fn dag_paths<W: Weight, G: GraphLike<Weight = W>>(
+    graph: &G,
+    start: usize,
+    improves: impl Fn(W, W) -> bool
+) -> Result<ShortestPaths<'_, W, G>, DagError> {
+    let order = topological_order(graph)?;
+    let nvert = graph.vertices().len();
+    let mut distance = vec![W::INFINITY; nvert];
+    let mut parent: Vec<Option<usize>> = vec![None; nvert];
+    distance[start] = W::ZERO;
+    for u in order {
+        if distance[u] == W::INFINITY { continue; }
+        for (v, weight) in graph.weighted_neighbors(u) {
+            // NOTE: `W::INFINITY` marks unreachable vertices, so a sum that lands on it overflows too
+            let candidate = distance[u].checked_add(weight)
+                .filter(|&candidate| candidate != W::INFINITY)
+                .ok_or(DagError::Overflow(u, v))?;
+            if improves(candidate, distance[v]) {
+                distance[v] = candidate;
+                parent[v] = Some(u);
+            }
+        }
+    }
+    Ok(ShortestPaths::new(graph, start, parent, distance))
+}
+
+// NOTE: vertices are events and edge weights are the durations of the activities between
+// them. Every vertex without predecessors may start at time zero
+pub(crate) fn critical_path<W: Weight, G: GraphLike<Weight = W>>(graph: &G) -> Result<CriticalPath<W>, DagError> {
+    let order = topological_order(graph)?;
+    let nvert = graph.vertices().len();
+    let mut earliest = vec![W::ZERO; nvert];
+    let mut parent: Vec<Option<usize>> = vec![None; nvert];
+    for &u in &order {
+        for (v, weight) in graph.weighted_neighbors(u) {
+            let candidate = earliest[u].checked_add(weight).ok_or(DagError::Overflow(u, v))?;
+            if candidate > earliest[v] || (parent[v].is_none() && candidate == earliest[v]) {
+                earliest[v] = candidate;
+                parent[v] = Some(u);
+            }
+        }
+    }
+
+    let finish = (0..nvert).max_by(|&a, &b| earliest[a].total_cmp(&earliest[b]));
+    let length = finish.map_or(W::ZERO, |v| earliest[v]);
+    let mut latest = vec![length; nvert];
+    for &u in order.iter().rev() {
+        for (v, weight) in graph.weighted_neighbors(u) {
+            let candidate = latest[v].checked_sub(weight).ok_or(DagError::Overflow(u, v))?;
+            if candidate < latest[u] { latest[u] = candidate; }
+        }
+    }
+
+    let mut chain = Vec::new();
+    let mut cur_vertex = finish;
+    while let Some(v) = cur_vertex {
+        chain.push(v);
+        cur_vertex = parent[v];
+    }
+    chain.reverse();
+    Ok(CriticalPath { earliest, latest, length, chain })
+}
+
+impl<W: Weight> CriticalPath<W> {
+    pub fn earliest_start(&self, v: usize) -> W {
+        self.earliest[v]
+    }
+
+    pub fn latest_start(&self, v: usize) -> W {
+        self.latest[v]
+    }
+
+    // NOTE: `earliest[v]` is never negative and `latest[v]` never above the length, so the slack
+    // lies between zero and the length
+    pub fn slack(&self, v: usize) -> W {
+        self.latest[v].checked_sub(self.earliest[v]).expect("the slack is at most the length")
+    }
+
+    pub fn is_critical(&self, v: usize) -> bool {
+        self.latest[v] == self.earliest[v]
+    }
+
+    pub fn length(&self) -> W {
+        self.length
+    }
+
+    pub fn critical_chain(&self) -> &[usize] {
+        &self.chain
+    }
+}
+
+impl fmt::Display for DagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DagError::Undirected => write!(f, "graph is undirected"),
+            DagError::Overflow(u, v) => write!(f, "path lengths overflow along {} -> {}", u, v),
+            DagError::Cycle(cycle) => {
+                write!(f, "cycle:")?;
+                cycle.iter().try_for_each(|v| write!(f, " {}", v))
+            },
+        }
+    }
+}
+
+impl std::error::Error for DagError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+    use crate::graphs::weighted::WeightedGraph;
+
+    fn pipeline() -> WeightedGraph {
+        // NOTE: 0 checkout -> {1 build, 2 lint} -> 3 test -> 5 package, 1 -> 4 docs -> 5
+        let mut graph = WeightedGraph::new(6, true);
+        graph.insert_edge(0, 1, 2);
+        graph.insert_edge(0, 2, 2);
+        graph.insert_edge(1, 3, 10);
+        graph.insert_edge(2, 3, 1);
+        graph.insert_edge(1, 4, 4);
+        graph.insert_edge(3, 5, 6);
+        graph.insert_edge(4, 5, 1);
+        graph
+    }
+
+    #[test]
+    fn topological_order() {
+        let graph = pipeline();
+        let order = super::topological_order(&graph).unwrap();
+        let position: Vec<usize> = (0..6).map(|v| order.iter().position(|&u| u == v).unwrap()).collect();
+        (0..6).for_each(|u| graph.incident(u).for_each(|(v, _)| assert!(position[u] < position[v])));
+
+        let mut graph = pipeline();
+        graph.insert_edge(5, 1, 1);
+        let mut cycle = match super::topological_order(&graph) {
+            Err(DagError::Cycle(cycle)) => cycle,
+            other => panic!("expected a cycle, got {:?}", other),
+        };
+        let min_idx = cycle.iter().enumerate().min_by_key(|(_, &v)| v).unwrap().0;
+        cycle.rotate_left(min_idx);
+        assert!(cycle == vec![1, 3, 5] || cycle == vec![1, 4, 5]);
+        assert!(matches!(graph.dag_shortest_paths(0), Err(DagError::Cycle(_))));
+
+        let graph: WeightedGraph = WeightedGraph::new(3, false);
+        assert_eq!(super::topological_order(&graph), Err(DagError::Undirected));
+    }
+
+    #[test]
+    fn shortest_and_longest() {
+        let graph = pipeline();
+        let shortest = graph.dag_shortest_paths(0).unwrap();
+        assert_eq!(shortest.distance, vec![0, 2, 2, 3, 6, 7]);
+        assert_eq!(shortest.path_to(5).unwrap().vertices(), &[0, 1, 4, 5]);
+        let longest = graph.dag_longest_paths(0).unwrap();
+        assert_eq!(longest.distance, vec![0, 2, 2, 12, 6, 18]);
+        assert_eq!(longest.path_to(5).unwrap().vertices(), &[0, 1, 3, 5]);
+
+        let longest = graph.dag_longest_paths(2).unwrap();
+        assert_eq!(longest.distance_to(1), None);
+        assert_eq!(longest.distance_to(5), Some(7));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX - 1);
+        graph.insert_edge(1, 2, 5);
+        assert_eq!(graph.dag_longest_paths(0).unwrap_err(), DagError::Overflow(1, 2));
+        assert_eq!(graph.dag_shortest_paths(0).unwrap_err(), DagError::Overflow(1, 2));
+        assert_eq!(graph.dag_longest_paths(1).unwrap().distance_to(2), Some(5));
+
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MIN);
+        graph.insert_edge(1, 2, -1);
+        assert_eq!(graph.dag_shortest_paths(0).unwrap_err(), DagError::Overflow(1, 2));
+        graph.insert_edge(0, 2, 1);
+        assert_eq!(graph.dag_longest_paths(0).unwrap_err(), DagError::Overflow(1, 2));
+    }
+
+    #[test]
+    fn critical_path() {
+        let critical_path = pipeline().critical_path().unwrap();
+        assert_eq!(critical_path.length(), 18);
+        assert_eq!(critical_path.critical_chain(), &[0, 1, 3, 5]);
+        assert_eq!((0..6).map(|v| critical_path.earliest_start(v)).collect::<Vec<_>>(), vec![0, 2, 2, 12, 6, 18]);
+        assert_eq!((0..6).map(|v| critical_path.latest_start(v)).collect::<Vec<_>>(), vec![0, 2, 11, 12, 17, 18]);
+        assert_eq!((0..6).map(|v| critical_path.slack(v)).collect::<Vec<_>>(), vec![0, 0, 9, 0, 11, 0]);
+        assert!(critical_path.is_critical(3) && !critical_path.is_critical(4));
+
+        let graph: WeightedGraph = WeightedGraph::new(0, true);
+        let critical_path = graph.critical_path().unwrap();
+        assert_eq!((critical_path.length(), critical_path.critical_chain().len()), (0, 0));
+
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX - 1);
+        graph.insert_edge(1, 2, 5);
+        assert_eq!(graph.critical_path().unwrap_err(), DagError::Overflow(1, 2));
+        let mut graph = WeightedGraph::new(2, true);
+        graph.insert_edge(0, 1, i32::MIN);
+        assert_eq!(graph.critical_path().unwrap_err(), DagError::Overflow(0, 1));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x7777111155553333);
+        for _ in 0..40 {
+            let nvert = 1 + rng.below(25);
+            let mut graph = WeightedGraph::new(nvert, true);
+            (0..rng.below(nvert * 3)).for_each(|_| {
+                let (u, v) = (rng.below(nvert), rng.below(nvert));
+                if u < v { graph.insert_edge(u, v, rng.below(30) as i32 - 10); }
+            });
+            let start = rng.below(nvert);
+            assert_eq!(graph.dag_shortest_paths(start).unwrap().distance, graph.bellman_ford(start).unwrap().distance);
+
+            // NOTE: longest paths are shortest paths with every weight negated
+            let mut negated = WeightedGraph::new(nvert, true);
+            (0..nvert).for_each(|u| graph.incident(u).for_each(|(v, w)| negated.insert_edge(u, v, -w)));
+            let expected: Vec<i32> = negated.bellman_ford(start).unwrap().distance.iter()
+                .map(|&d| if d == i32::MAX { d } else { -d })
+                .collect();
+            assert_eq!(graph.dag_longest_paths(start).unwrap().distance, expected);
+        }
+    }
+}
@@ -0,0 +1,519 @@
+use std::{
+    fmt,
+    collections::VecDeque
+};
+
+use crate::containers::priority_queue::heap;
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::weighted::{WeightedGraph, PathError};
+
+#[derive(Debug, Clone)]
+pub struct FlowNetwork {
+    // NOTE: every arc is stored next to its residual twin, so `edges[e ^ 1]` is the reverse of
+    // `edges[e]` and original arcs live at the even indices
+    edges: Vec<FlowEdge>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FlowEdge {
+    from: usize,
+    points_to: usize,
+    capacity: i32,
+    // NOTE: widened so that the residual twin can hold the negation of `i32::MIN`
+    cost: i64,
+    flow: i32,
+}
+
+// NOTE: the value sums capacities that each fit in an `i32`, so it is kept as an `i64`
+#[derive(Debug)]
+pub struct MaxFlow {
+    value: i64,
+    flows: Vec<(usize, usize, i32)>,
+    source_side: Vec<bool>,
+}
+
+// NOTE: a min-cost maximum flow along with its total cost
+pub type MinCostFlow = (MaxFlow, i64);
+
+#[derive(Debug, PartialEq)]
+pub enum FlowError {
+    NegativeCapacity(usize, usize),
+}
+
+impl FlowNetwork {
+    pub fn new(vcapacity: usize) -> Self {
+        FlowNetwork { edges: Vec::new(), adjacency: vec![Vec::new(); vcapacity] }
+    }
+
+    pub fn insert_edge(&mut self, i: usize, j: usize, capacity: i32) {
+        self.insert_costed_edge(i, j, capacity, 0);
+    }
+
+    pub fn insert_costed_edge(&mut self, i: usize, j: usize, capacity: i32, cost: i32) {
+        assert!(j < self.adjacency.len() && i < self.adjacency.len(), "vertices `i` and `j` must be within capacity");
+        assert!(capacity >= 0, "capacities must be non-negative");
+        self.adjacency[i].push(self.edges.len());
+        let cost = i64::from(cost);
+        self.edges.push(FlowEdge { from: i, points_to: j, capacity, cost, flow: 0 });
+        self.adjacency[j].push(self.edges.len());
+        self.edges.push(FlowEdge { from: j, points_to: i, capacity: 0, cost: -cost, flow: 0 });
+    }
+
+    pub(crate) fn vcapacity(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    // NOTE: the arcs in insertion order as `(from, points_to, capacity)`, without their residual twins
+    pub(crate) fn arcs(&self) -> impl Iterator<Item = (usize, usize, i32)> + '_ {
+        self.edges.iter().step_by(2).map(|edge| (edge.from, edge.points_to, edge.capacity))
+    }
+
+    pub fn max_flow(&self, source: usize, sink: usize) -> MaxFlow {
+        self.dinic(source, sink)
+    }
+
+    pub fn edmonds_karp(&self, source: usize, sink: usize) -> MaxFlow {
+        assert!(source != sink, "`source` and `sink` must differ");
+        let mut network = self.clone();
+        let mut value = 0;
+        // NOTE: augmenting along shortest (BFS) paths bounds the number of rounds by O(VE)
+        loop {
+            let parent_edge = network.bfs_tree(source);
+            if parent_edge[sink].is_none() { break; }
+            let mut bottleneck = i32::MAX;
+            let mut cur_vertex = sink;
+            while let Some(e) = parent_edge[cur_vertex] {
+                bottleneck = bottleneck.min(network.edges[e].residual());
+                cur_vertex = network.edges[e].from;
+            }
+            cur_vertex = sink;
+            while let Some(e) = parent_edge[cur_vertex] {
+                network.push(e, bottleneck);
+                cur_vertex = network.edges[e].from;
+            }
+            value += i64::from(bottleneck);
+        }
+        network.into_max_flow(source, value)
+    }
+
+    pub fn dinic(&self, source: usize, sink: usize) -> MaxFlow {
+        assert!(source != sink, "`source` and `sink` must differ");
+        let mut network = self.clone();
+        let mut value = 0;
+        loop {
+            let level = network.levels(source);
+            if level[sink].is_none() { break; }
+            // NOTE: `next_edge[v]` remembers which arcs of `v` are already saturated or lead to
+            // dead ends in this phase, which is what keeps each phase at O(VE)
+            let mut next_edge = vec![0; network.adjacency.len()];
+            loop {
+                let pushed = network.blocking_flow(source, sink, &level, &mut next_edge);
+                if pushed == 0 { break; }
+                value += i64::from(pushed);
+            }
+        }
+        network.into_max_flow(source, value)
+    }
+
+    // NOTE: successive shortest paths; always augmenting along the cheapest residual path keeps
+    // the flow min-cost for its value, so the final flow is a min-cost maximum flow
+    pub fn min_cost_flow(&self, source: usize, sink: usize) -> Result<MinCostFlow, PathError> {
+        assert!(source != sink, "`source` and `sink` must differ");
+        let mut network = self.clone();
+        let nvert = network.adjacency.len();
+
+        // NOTE: costs may be negative, so the first potentials come from Bellman-Ford; after that
+        // reduced costs stay non-negative and every round can use Dijkstra. Potentials are path
+        // costs, at most V times the largest `i32` cost in size, so `i64` arithmetic cannot
+        // overflow on them
+        let mut residual: WeightedGraph<i64> = WeightedGraph::new(nvert, true);
+        network.edges.iter().filter(|edge| edge.residual() > 0).for_each(|edge| {
+            residual.insert_edge(edge.from, edge.points_to, edge.cost);
+        });
+        let mut potential: Vec<i64> = residual.bellman_ford(source)?.distance.iter()
+            .map(|&d| if d == i64::MAX { 0 } else { d })
+            .collect();
+
+        let mut value = 0;
+        loop {
+            let mut distance = vec![i64::MAX; nvert];
+            let mut parent_edge: Vec<Option<usize>> = vec![None; nvert];
+            let mut intree = vec![false; nvert];
+            let mut queue: heap::Heap<(i64, usize)> = heap::Heap::new();
+            distance[source] = 0;
+            queue.insert((0, source));
+            while let Some((_, v)) = queue.pop() {
+                if intree[v] { continue; }
+                intree[v] = true;
+                for &e in &network.adjacency[v] {
+                    let edge = network.edges[e];
+                    if edge.residual() == 0 { continue; }
+                    let reduced = edge.cost + potential[v] - potential[edge.points_to];
+                    if distance[edge.points_to] > distance[v] + reduced {
+                        distance[edge.points_to] = distance[v] + reduced;
+                        parent_edge[edge.points_to] = Some(e);
+                        queue.insert((distance[edge.points_to], edge.points_to));
+                    }
+                }
+            }
+            if !intree[sink] { break; }
+            (0..nvert).filter(|&v| intree[v]).for_each(|v| potential[v] += distance[v]);
+
+            let mut bottleneck = i32::MAX;
+            let mut cur_vertex = sink;
+            while let Some(e) = parent_edge[cur_vertex] {
+                bottleneck = bottleneck.min(network.edges[e].residual());
+                cur_vertex = network.edges[e].from;
+            }
+            cur_vertex = sink;
+            while let Some(e) = parent_edge[cur_vertex] {
+                network.push(e, bottleneck);
+                cur_vertex = network.edges[e].from;
+            }
+            value += i64::from(bottleneck);
+        }
+        // NOTE: each product fits in an `i64`, but their sum need not
+        let cost = network.edges.iter().step_by(2).try_fold(0i64, |cost, edge| {
+            cost.checked_add(i64::from(edge.flow) * edge.cost).ok_or(PathError::Overflow(edge.from, edge.points_to))
+        })?;
+        Ok((network.into_max_flow(source, value), cost))
+    }
+
+    fn push(&mut self, e: usize, amount: i32) {
+        self.edges[e].flow += amount;
+        self.edges[e ^ 1].flow -= amount;
+    }
+
+    fn bfs_tree(&self, source: usize) -> Vec<Option<usize>> {
+        let mut parent_edge: Vec<Option<usize>> = vec![None; self.adjacency.len()];
+        let mut discovered = vec![false; self.adjacency.len()];
+        let mut queue = VecDeque::new();
+        discovered[source] = true;
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            for &e in &self.adjacency[v] {
+                let edge = self.edges[e];
+                if !discovered[edge.points_to] && edge.residual() > 0 {
+                    discovered[edge.points_to] = true;
+                    parent_edge[edge.points_to] = Some(e);
+                    queue.push_back(edge.points_to);
+                }
+            }
+        }
+        parent_edge
+    }
+
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut level = vec![None; self.adjacency.len()];
+        let mut queue = VecDeque::new();
+        level[source] = Some(0);
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            for &e in &self.adjacency[v] {
+                let edge = self.edges[e];
+                if level[edge.points_to].is_none() && edge.residual() > 0 {
+                    level[edge.points_to] = level[v].map(|l| l + 1);
+                    queue.push_back(edge.points_to);
+                }
+            }
+        }
+        level
+    }
+
+    // NOTE: one augmenting path through the level graph, grown on an explicit stack of arcs so
+    // that long paths cannot overflow the call stack. Retreating from a dead end skips the arc
+    // that led into it for the rest of the phase
+    fn blocking_flow(&mut self, source: usize, sink: usize, level: &[Option<usize>], next_edge: &mut [usize]) -> i32 {
+        let mut path: Vec<usize> = Vec::new();
+        let mut v = source;
+        while v != sink {
+            let admissible = self.adjacency[v][next_edge[v]..].iter().position(|&e| {
+                let edge = self.edges[e];
+                edge.residual() > 0 && level[edge.points_to] == level[v].map(|l| l + 1)
+            });
+            match admissible {
+                Some(offset) => {
+                    next_edge[v] += offset;
+                    let e = self.adjacency[v][next_edge[v]];
+                    path.push(e);
+                    v = self.edges[e].points_to;
+                },
+                None => {
+                    next_edge[v] = self.adjacency[v].len();
+                    match path.pop() {
+                        Some(e) => {
+                            v = self.edges[e].from;
+                            next_edge[v] += 1;
+                        },
+                        None => return 0,
+                    }
+                },
+            }
+        }
+        let pushed = path.iter().map(|&e| self.edges[e].residual()).min().expect("`source` and `sink` differ");
+        path.iter().for_each(|&e| self.push(e, pushed));
+        pushed
+    }
+
+    fn into_max_flow(self, source: usize, value: i64) -> MaxFlow {
+        // NOTE: the vertices still reachable in the residual network form the source side of a
+        // minimum cut
+        let parent_edge = self.bfs_tree(source);
+        let source_side = (0..self.adjacency.len()).map(|v| v == source || parent_edge[v].is_some()).collect();
+        let flows = self.edges.iter().step_by(2).map(|edge| (edge.from, edge.points_to, edge.flow)).collect();
+        MaxFlow { value, flows, source_side }
+    }
+}
+
+// NOTE: edge weights become capacities, so a negative weight is an error rather than an arc
+impl TryFrom<&WeightedGraph> for FlowNetwork {
+    type Error = FlowError;
+
+    fn try_from(graph: &WeightedGraph) -> Result<Self, Self::Error> {
+        let mut network = FlowNetwork::new(graph.vcapacity());
+        for u in 0..graph.vcapacity() {
+            for (v, capacity) in graph.incident(u) {
+                if capacity < 0 { return Err(FlowError::NegativeCapacity(u, v)); }
+                network.insert_edge(u, v, capacity);
+            }
+        }
+        Ok(network)
+    }
+}
+
+impl FlowEdge {
+    fn residual(&self) -> i32 {
+        self.capacity - self.flow
+    }
+}
+
+impl MaxFlow {
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    // NOTE: one `(from, to, flow)` triple per arc, in insertion order
+    pub fn flows(&self) -> &[(usize, usize, i32)] {
+        &self.flows
+    }
+
+    pub fn min_cut(&self) -> (Vec<usize>, Vec<usize>) {
+        (0..self.source_side.len()).partition(|&v| self.source_side[v])
+    }
+
+    pub fn cut_edges(&self) -> Vec<(usize, usize)> {
+        self.flows.iter()
+            .filter(|(from, to, _)| self.source_side[*from] && !self.source_side[*to])
+            .map(|&(from, to, _)| (from, to))
+            .collect()
+    }
+}
+
+impl fmt::Display for FlowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowError::NegativeCapacity(u, v) => write!(f, "edge {} -> {} has a negative capacity", u, v),
+        }
+    }
+}
+
+impl std::error::Error for FlowError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    fn check(network: &FlowNetwork, max_flow: &MaxFlow, source: usize, sink: usize) {
+        let mut balance = vec![0i64; network.adjacency.len()];
+        max_flow.flows().iter().zip(network.edges.iter().step_by(2)).for_each(|(&(from, to, flow), edge)| {
+            assert!(0 <= flow && flow <= edge.capacity);
+            balance[from] -= i64::from(flow);
+            balance[to] += i64::from(flow);
+        });
+        (0..balance.len()).filter(|&v| v != source && v != sink).for_each(|v| assert_eq!(balance[v], 0));
+        assert_eq!(balance[sink], max_flow.value());
+        let cut_capacity: i64 = network.edges.iter().step_by(2)
+            .filter(|edge| max_flow.source_side[edge.from] && !max_flow.source_side[edge.points_to])
+            .map(|edge| i64::from(edge.capacity))
+            .sum();
+        assert_eq!(cut_capacity, max_flow.value());
+    }
+
+    fn clrs() -> WeightedGraph {
+        let mut graph = WeightedGraph::new(6, true);
+        graph.insert_edge(0, 1, 16);
+        graph.insert_edge(0, 2, 13);
+        graph.insert_edge(1, 3, 12);
+        graph.insert_edge(2, 1, 4);
+        graph.insert_edge(2, 4, 14);
+        graph.insert_edge(3, 2, 9);
+        graph.insert_edge(3, 5, 20);
+        graph.insert_edge(4, 3, 7);
+        graph.insert_edge(4, 5, 4);
+        graph
+    }
+
+    #[test]
+    fn edmonds_karp() {
+        let network = FlowNetwork::try_from(&clrs()).unwrap();
+        let max_flow = network.edmonds_karp(0, 5);
+        assert_eq!(max_flow.value(), 23);
+        check(&network, &max_flow, 0, 5);
+        assert_eq!(max_flow.min_cut(), (vec![0, 1, 2, 4], vec![3, 5]));
+        let mut cut_edges = max_flow.cut_edges();
+        cut_edges.sort();
+        assert_eq!(cut_edges, vec![(1, 3), (4, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn dinic() {
+        let network = FlowNetwork::try_from(&clrs()).unwrap();
+        let max_flow = network.dinic(0, 5);
+        assert_eq!(max_flow.value(), 23);
+        check(&network, &max_flow, 0, 5);
+        assert_eq!(max_flow.min_cut(), (vec![0, 1, 2, 4], vec![3, 5]));
+    }
+
+    #[test]
+    fn undirected() {
+        // NOTE: undirected edges can carry flow in either direction
+        let mut graph = WeightedGraph::new(6, false);
+        graph.insert_edge(0, 1, 5);
+        graph.insert_edge(0, 2, 12);
+        graph.insert_edge(1, 3, 7);
+        graph.insert_edge(2, 3, 9);
+        graph.insert_edge(2, 4, 4);
+        graph.insert_edge(3, 5, 3);
+        graph.insert_edge(4, 5, 7);
+        graph.insert_edge(1, 4, 2);
+        let network = FlowNetwork::try_from(&graph).unwrap();
+        let max_flow = network.max_flow(0, 5);
+        assert_eq!(max_flow.value(), 9);
+        check(&network, &max_flow, 0, 5);
+        assert_eq!(network.edmonds_karp(0, 5).value(), 9);
+    }
+
+    #[test]
+    fn negative_capacity() {
+        let mut graph = clrs();
+        graph.insert_edge(4, 2, -1);
+        let error = FlowNetwork::try_from(&graph).unwrap_err();
+        assert_eq!(error, FlowError::NegativeCapacity(4, 2));
+        assert_eq!(error.to_string(), "edge 4 -> 2 has a negative capacity");
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut network = FlowNetwork::new(4);
+        network.insert_edge(0, 1, 3);
+        network.insert_edge(2, 3, 3);
+        let max_flow = network.dinic(0, 3);
+        assert_eq!(max_flow.value(), 0);
+        assert_eq!(max_flow.min_cut(), (vec![0, 1], vec![2, 3]));
+        assert!(max_flow.cut_edges().is_empty());
+    }
+
+    #[test]
+    fn long_path() {
+        let nvert = 300_000;
+        let mut network = FlowNetwork::new(nvert);
+        (1..nvert).for_each(|v| network.insert_edge(v - 1, v, 1 + (v % 7) as i32));
+        assert_eq!(network.dinic(0, nvert - 1).value(), 1);
+        assert_eq!(network.edmonds_karp(0, nvert - 1).value(), 1);
+    }
+
+    #[test]
+    fn large_capacities() {
+        let mut network = FlowNetwork::new(3);
+        network.insert_edge(0, 1, i32::MAX);
+        network.insert_edge(0, 2, i32::MAX);
+        network.insert_edge(1, 2, i32::MAX);
+        let expected = 2 * i64::from(i32::MAX);
+        assert_eq!(network.max_flow(0, 2).value(), expected);
+        assert_eq!(network.edmonds_karp(0, 2).value(), expected);
+        check(&network, &network.max_flow(0, 2), 0, 2);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x0123456789abcdef);
+        for round in 0..50 {
+            let nvert = 2 + rng.below(20);
+            let nedges = rng.below(nvert * 5);
+            let graph = rng.weighted_graph(nvert, nedges, round % 3 != 0, 0..15);
+            let network = FlowNetwork::try_from(&graph).unwrap();
+            let (source, sink) = (0, nvert - 1);
+            let edmonds_karp = network.edmonds_karp(source, sink);
+            let dinic = network.dinic(source, sink);
+            assert_eq!(edmonds_karp.value(), dinic.value());
+            check(&network, &edmonds_karp, source, sink);
+            check(&network, &dinic, source, sink);
+        }
+    }
+
+    #[test]
+    fn min_cost_flow() {
+        let mut network = FlowNetwork::new(4);
+        network.insert_costed_edge(0, 1, 4, 2);
+        network.insert_costed_edge(0, 2, 2, 2);
+        network.insert_costed_edge(1, 2, 2, 1);
+        network.insert_costed_edge(1, 3, 3, 3);
+        network.insert_costed_edge(2, 3, 5, 1);
+        let (min_cost_flow, cost) = network.min_cost_flow(0, 3).unwrap();
+        assert_eq!(min_cost_flow.value(), 6);
+        assert_eq!(cost, 24);
+        check(&network, &min_cost_flow, 0, 3);
+        assert_eq!(min_cost_flow.flows(), &[(0, 1, 4), (0, 2, 2), (1, 2, 2), (1, 3, 2), (2, 3, 4)]);
+    }
+
+    #[test]
+    fn min_cost_flow_negative_costs() {
+        let mut network = FlowNetwork::new(4);
+        network.insert_costed_edge(0, 1, 1, 5);
+        network.insert_costed_edge(0, 2, 1, 1);
+        network.insert_costed_edge(1, 3, 1, -4);
+        network.insert_costed_edge(2, 3, 1, 1);
+        network.insert_costed_edge(2, 1, 1, -3);
+        let (min_cost_flow, cost) = network.min_cost_flow(0, 3).unwrap();
+        assert_eq!((min_cost_flow.value(), cost), (2, 3));
+
+        network.insert_costed_edge(1, 2, 1, 1);
+        assert!(matches!(network.min_cost_flow(0, 3), Err(PathError::NegativeCycle(_))));
+    }
+
+    #[test]
+    fn min_cost_flow_large_costs() {
+        let mut network = FlowNetwork::new(4);
+        network.insert_costed_edge(0, 1, 2, i32::MAX);
+        network.insert_costed_edge(1, 2, 2, i32::MAX);
+        network.insert_costed_edge(2, 3, 2, i32::MIN);
+        network.insert_costed_edge(0, 3, 1, i32::MAX);
+        let (min_cost_flow, cost) = network.min_cost_flow(0, 3).unwrap();
+        assert_eq!(min_cost_flow.value(), 3);
+        assert_eq!(cost, 2 * (2 * i64::from(i32::MAX) + i64::from(i32::MIN)) + i64::from(i32::MAX));
+
+        let mut network = FlowNetwork::new(2);
+        network.insert_costed_edge(0, 1, i32::MAX, i32::MAX);
+        network.insert_costed_edge(0, 1, i32::MAX, i32::MAX);
+        network.insert_costed_edge(0, 1, i32::MAX, i32::MAX);
+        assert_eq!(network.min_cost_flow(0, 1).unwrap_err(), PathError::Overflow(0, 1));
+    }
+
+    #[test]
+    fn min_cost_flow_random() {
+        let mut rng = XorShift(0xfedcba9876543210);
+        for _ in 0..50 {
+            let nvert = 2 + rng.below(15);
+            let mut network = FlowNetwork::new(nvert);
+            (0..rng.below(nvert * 4)).for_each(|_| {
+                network.insert_costed_edge(rng.below(nvert), rng.below(nvert), rng.below(10) as i32, rng.below(10) as i32);
+            });
+            let (source, sink) = (0, nvert - 1);
+            let (min_cost_flow, _) = network.min_cost_flow(source, sink).unwrap();
+            assert_eq!(min_cost_flow.value(), network.max_flow(source, sink).value());
+            check(&network, &min_cost_flow, source, sink);
+        }
+    }
+}
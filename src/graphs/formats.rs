@@ -0,0 +1,610 @@
+use std::{
+    fmt,
+    str::FromStr,
+    cmp::Ordering,
+    collections::HashMap
+};
+
+use crate::graphs::flow::FlowNetwork;
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::unweighted::Graph;
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::{WeightedGraph, Path, MinSpanTree};
+
+// NOTE: `line` counts from 1. Problems only noticed at the end of the input (a missing header, too
+// few edges) point at the last line
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    line: usize,
+    kind: ParseErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseErrorKind {
+    MissingHeader,
+    DuplicateHeader,
+    Malformed(String),
+    InvalidNumber(String),
+    VertexOutOfRange(usize),
+    TooManyVertices(usize),
+    EdgeCount { expected: usize, found: usize },
+    WrongProblem(String),
+    NegativeCapacity(i32),
+    MissingTerminal(&'static str),
+    SourceIsSink(usize),
+    Asymmetric(usize, usize),
+}
+
+// NOTE: the most vertices a header may announce. Every graph allocates its adjacency lists up front,
+// so a larger count in a small file is far more likely a typo than a real graph, and would abort on
+// allocation failure instead of reporting an error
+pub const MAX_VERTICES: usize = 1 << 26;
+
+#[derive(Debug, PartialEq)]
+pub enum HighlightError {
+    ComponentCount { expected: usize, found: usize },
+}
+
+// NOTE: a max-flow instance as read from DIMACS: the network, its source and its sink
+pub type FlowProblem = (FlowNetwork, usize, usize);
+
+// NOTE: what `write_dot` can emphasize. Path and tree edges are drawn bold and red; a component
+// coloring fills every vertex with the color of its component id
+pub enum Highlight<'a, W: Weight> {
+    Path(&'a Path<W>),
+    Tree(&'a MinSpanTree<'a, W>),
+    Components(&'a [usize]),
+}
+
+// NOTE: ADM's format: a `nvert nedges` header, then one `u v` line per edge with vertices numbered
+// from 0. Blank lines and lines starting with `#` are skipped
+pub fn read_edge_list(text: &str, directed: bool) -> Result<Graph, ParseError> {
+    let (nvert, edges) = parse_edge_list(text, |tokens, line, nvert| {
+        let [u, v] = fields(tokens, line)?;
+        Ok((vertex(u, line, nvert, 0)?, vertex(v, line, nvert, 0)?))
+    })?;
+    let mut graph = Graph::new(nvert, directed);
+    edges.into_iter().for_each(|(u, v)| graph.insert_edge(u, v));
+    Ok(graph)
+}
+
+// NOTE: as `read_edge_list`, with a third `weight` column
+pub fn read_weighted_edge_list<W: Weight + FromStr>(text: &str, directed: bool) -> Result<WeightedGraph<W>, ParseError> {
+    let (nvert, edges) = parse_edge_list(text, |tokens, line, nvert| {
+        let [u, v, weight] = fields(tokens, line)?;
+        Ok((vertex(u, line, nvert, 0)?, vertex(v, line, nvert, 0)?, number(weight, line)?))
+    })?;
+    let mut graph = WeightedGraph::new(nvert, directed);
+    edges.into_iter().for_each(|(u, v, weight)| graph.insert_edge(u, v, weight));
+    Ok(graph)
+}
+
+pub fn write_edge_list(graph: &impl GraphLike) -> String {
+    let edges = edges_once(graph);
+    let mut text = format!("{} {}\n", graph.vertices().len(), edges.len());
+    edges.into_iter().for_each(|(u, v, _)| text.push_str(&format!("{} {}\n", u, v)));
+    text
+}
+
+pub fn write_weighted_edge_list(graph: &impl GraphLike) -> String {
+    let edges = edges_once(graph);
+    let mut text = format!("{} {}\n", graph.vertices().len(), edges.len());
+    edges.into_iter().for_each(|(u, v, weight)| text.push_str(&format!("{} {} {}\n", u, v, weight)));
+    text
+}
+
+// NOTE: the DIMACS shortest path format: `c` comment lines, a `p sp nvert narcs` problem line and
+// one `a u v weight` line per arc, with vertices numbered from 1. Arcs are always directed
+pub fn read_dimacs_graph<W: Weight + FromStr>(text: &str) -> Result<WeightedGraph<W>, ParseError> {
+    let (nvert, narcs, lines) = parse_dimacs(text, "sp")?;
+    let mut graph = WeightedGraph::new(nvert, true);
+    for (line, tokens) in lines {
+        match tokens.as_slice() {
+            ["a", u, v, weight] => graph.insert_edge(vertex(u, line, nvert, 1)?, vertex(v, line, nvert, 1)?, number(weight, line)?),
+            _ => return Err(ParseError::new(line, ParseErrorKind::Malformed(tokens.join(" ")))),
+        }
+    }
+    check_count(text, narcs, graph.edge_count())?;
+    Ok(graph)
+}
+
+// NOTE: an undirected edge becomes a pair of opposite arcs
+pub fn write_dimacs_graph(graph: &impl GraphLike) -> String {
+    let mut arcs = Vec::new();
+    edges_once(graph).into_iter().for_each(|(u, v, weight)| {
+        arcs.push((u, v, weight));
+        if !graph.is_directed() && u != v { arcs.push((v, u, weight)); }
+    });
+    let mut text = format!("p sp {} {}\n", graph.vertices().len(), arcs.len());
+    arcs.into_iter().for_each(|(u, v, weight)| text.push_str(&format!("a {} {} {}\n", u + 1, v + 1, weight)));
+    text
+}
+
+// NOTE: the DIMACS max-flow format: a `p max nvert narcs` problem line, `n id s` and `n id t` lines
+// naming the source and the sink, and one `a u v capacity` line per arc
+pub fn read_dimacs_flow(text: &str) -> Result<FlowProblem, ParseError> {
+    let (nvert, narcs, lines) = parse_dimacs(text, "max")?;
+    let mut network = FlowNetwork::new(nvert);
+    let (mut source, mut sink, mut found) = (None, None, 0);
+    for (line, tokens) in lines {
+        match tokens.as_slice() {
+            ["n", v, "s"] if source.is_none() => source = Some((vertex(v, line, nvert, 1)?, line)),
+            ["n", v, "t"] if sink.is_none() => sink = Some((vertex(v, line, nvert, 1)?, line)),
+            ["a", u, v, capacity] => {
+                let (u, v, capacity): (usize, usize, i32) = (vertex(u, line, nvert, 1)?, vertex(v, line, nvert, 1)?, number(capacity, line)?);
+                if capacity < 0 { return Err(ParseError::new(line, ParseErrorKind::NegativeCapacity(capacity))); }
+                network.insert_edge(u, v, capacity);
+                found += 1;
+            },
+            _ => return Err(ParseError::new(line, ParseErrorKind::Malformed(tokens.join(" ")))),
+        }
+    }
+    let (source, source_line) = source.ok_or_else(|| ParseError::new(last_line(text), ParseErrorKind::MissingTerminal("source")))?;
+    let (sink, sink_line) = sink.ok_or_else(|| ParseError::new(last_line(text), ParseErrorKind::MissingTerminal("sink")))?;
+    // NOTE: `max_flow` needs two distinct terminals, so the later of the two lines is at fault
+    if source == sink { return Err(ParseError::new(source_line.max(sink_line), ParseErrorKind::SourceIsSink(source + 1))); }
+    check_count(text, narcs, found)?;
+    Ok((network, source, sink))
+}
+
+pub fn write_dimacs_flow(network: &FlowNetwork, source: usize, sink: usize) -> String {
+    let arcs: Vec<(usize, usize, i32)> = network.arcs().collect();
+    let mut text = format!("p max {} {}\nn {} s\nn {} t\n", network.vcapacity(), arcs.len(), source + 1, sink + 1);
+    arcs.into_iter().for_each(|(u, v, capacity)| text.push_str(&format!("a {} {} {}\n", u + 1, v + 1, capacity)));
+    text
+}
+
+// NOTE: a `nvert` header followed by `u: v1 v2 ...` lines, the format `Graph` displays in. Vertices
+// without a line have no edges. Undirected graphs list every edge at both endpoints (a self-loop
+// twice at its vertex), and both sides have to agree
+pub fn read_adjacency_list(text: &str, directed: bool) -> Result<Graph, ParseError> {
+    let (nvert, edges) = parse_adjacency_list(text, directed, |token, line, nvert| Ok((vertex(token, line, nvert, 0)?, ())))?;
+    let mut graph = Graph::new(nvert, directed);
+    edges.into_iter().for_each(|(u, v, _)| graph.insert_edge(u, v));
+    Ok(graph)
+}
+
+// NOTE: as `read_adjacency_list` with `v[w:weight]` entries, the format `WeightedGraph` displays in
+pub fn read_weighted_adjacency_list<W: Weight + FromStr>(text: &str, directed: bool) -> Result<WeightedGraph<W>, ParseError> {
+    let (nvert, edges) = parse_adjacency_list(text, directed, |token, line, nvert| {
+        let malformed = || ParseError::new(line, ParseErrorKind::Malformed(token.to_string()));
+        let (v, weight) = token.strip_suffix(']').and_then(|entry| entry.split_once("[w:")).ok_or_else(malformed)?;
+        Ok((vertex(v, line, nvert, 0)?, number(weight, line)?))
+    })?;
+    let mut graph = WeightedGraph::new(nvert, directed);
+    edges.into_iter().for_each(|(u, v, weight)| graph.insert_edge(u, v, weight));
+    Ok(graph)
+}
+
+pub fn write_adjacency_list(graph: &impl GraphLike) -> String {
+    let mut text = format!("{}\n", graph.vertices().len());
+    graph.vertices().for_each(|u| {
+        text.push_str(&format!("{}:", u));
+        graph.neighbors(u).for_each(|v| text.push_str(&format!(" {}", v)));
+        text.push('\n');
+    });
+    text
+}
+
+pub fn write_weighted_adjacency_list(graph: &impl GraphLike) -> String {
+    let mut text = format!("{}\n", graph.vertices().len());
+    graph.vertices().for_each(|u| {
+        text.push_str(&format!("{}:", u));
+        graph.weighted_neighbors(u).for_each(|(v, weight)| text.push_str(&format!(" {}[w:{}]", v, weight)));
+        text.push('\n');
+    });
+    text
+}
+
+// NOTE: Graphviz source with a line per vertex and per edge (undirected edges once), the edges
+// labelled with their weights when `show_weights` is set. A component coloring needs one entry
+// per vertex
+pub fn write_dot<G: GraphLike>(graph: &G, show_weights: bool, highlight: Option<Highlight<'_, G::Weight>>) -> Result<String, HighlightError> {
+    if let Some(Highlight::Components(component)) = &highlight {
+        let (expected, found) = (graph.vertices().len(), component.len());
+        if expected != found { return Err(HighlightError::ComponentCount { expected, found }); }
+    }
+    let directed = graph.is_directed();
+    let normalize = |u: usize, v: usize| if directed { (u, v) } else { (u.min(v), u.max(v)) };
+    let (marked_vertices, marked_edges): (Vec<usize>, Vec<(usize, usize)>) = match &highlight {
+        Some(Highlight::Path(path)) => (path.vertices().to_vec(), path.edges().map(|(u, v)| normalize(u, v)).collect()),
+        Some(Highlight::Tree(tree)) => (Vec::new(), tree.edges().iter().map(|&(u, v, _)| normalize(u, v)).collect()),
+        Some(Highlight::Components(_)) | None => (Vec::new(), Vec::new()),
+    };
+
+    let mut text = String::from(if directed { "digraph {\n" } else { "graph {\n" });
+    graph.vertices().for_each(|v| {
+        let mut attributes = Vec::new();
+        if marked_vertices.contains(&v) { attributes.push(String::from("color=red")); }
+        if let Some(Highlight::Components(component)) = &highlight {
+            attributes.push(format!("style=filled, fillcolor=\"/set312/{}\"", component[v] % 12 + 1));
+        }
+        text.push_str(&format!("    {}{};\n", v, dot_attributes(&attributes)));
+    });
+    let arrow = if directed { "->" } else { "--" };
+    edges_once(graph).into_iter().for_each(|(u, v, weight)| {
+        let mut attributes = Vec::new();
+        if show_weights { attributes.push(format!("label=\"{}\"", weight)); }
+        if marked_edges.contains(&normalize(u, v)) { attributes.push(String::from("color=red, penwidth=2")); }
+        text.push_str(&format!("    {} {} {}{};\n", u, arrow, v, dot_attributes(&attributes)));
+    });
+    text.push_str("}\n");
+    Ok(text)
+}
+
+impl ParseError {
+    fn new(line: usize, kind: ParseErrorKind) -> Self {
+        ParseError { line, kind }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+// NOTE: the non-blank lines of `text` that are not comments, numbered from 1 and split on whitespace
+fn content_lines<'a>(text: &'a str, comment: &'a str) -> impl Iterator<Item = (usize, Vec<&'a str>)> + 'a {
+    text.lines().enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(move |(_, line)| !line.is_empty() && !line.starts_with(comment))
+        .map(|(line, content)| (line, content.split_whitespace().collect()))
+}
+
+fn last_line(text: &str) -> usize {
+    text.lines().count().max(1)
+}
+
+fn fields<'a, const N: usize>(tokens: &[&'a str], line: usize) -> Result<[&'a str; N], ParseError> {
+    tokens.try_into().map_err(|_| ParseError::new(line, ParseErrorKind::Malformed(tokens.join(" "))))
+}
+
+fn number<T: FromStr>(token: &str, line: usize) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::new(line, ParseErrorKind::InvalidNumber(token.to_string())))
+}
+
+// NOTE: `first` is the id the format gives to vertex 0, which is 1 for DIMACS
+fn vertex(token: &str, line: usize, nvert: usize, first: usize) -> Result<usize, ParseError> {
+    let id: usize = number(token, line)?;
+    if id < first || id - first >= nvert { return Err(ParseError::new(line, ParseErrorKind::VertexOutOfRange(id))); }
+    Ok(id - first)
+}
+
+fn vertex_count(token: &str, line: usize) -> Result<usize, ParseError> {
+    let nvert: usize = number(token, line)?;
+    if nvert > MAX_VERTICES { return Err(ParseError::new(line, ParseErrorKind::TooManyVertices(nvert))); }
+    Ok(nvert)
+}
+
+fn check_count(text: &str, expected: usize, found: usize) -> Result<(), ParseError> {
+    if expected == found { return Ok(()); }
+    Err(ParseError::new(last_line(text), ParseErrorKind::EdgeCount { expected, found }))
+}
+
+fn parse_edge_list<T>(
+    text: &str,
+    mut record: impl FnMut(&[&str], usize, usize) -> Result<T, ParseError>
+) -> Result<(usize, Vec<T>), ParseError> {
+    let mut lines = content_lines(text, "#");
+    let (line, header) = lines.next().ok_or_else(|| ParseError::new(last_line(text), ParseErrorKind::MissingHeader))?;
+    let [nvert, nedges] = fields(&header, line)?;
+    let (nvert, nedges) = (vertex_count(nvert, line)?, number(nedges, line)?);
+    let records = lines.map(|(line, tokens)| record(&tokens, line, nvert)).collect::<Result<Vec<T>, _>>()?;
+    check_count(text, nedges, records.len())?;
+    Ok((nvert, records))
+}
+
+// NOTE: numbered lines split into tokens
+type Lines<'a> = Vec<(usize, Vec<&'a str>)>;
+
+// NOTE: returns the vertex and arc counts of the problem line along with the lines after it
+fn parse_dimacs<'a>(text: &'a str, problem: &str) -> Result<(usize, usize, Lines<'a>), ParseError> {
+    let mut lines = content_lines(text, "c");
+    let (line, header) = lines.next().ok_or_else(|| ParseError::new(last_line(text), ParseErrorKind::MissingHeader))?;
+    let [p, kind, nvert, narcs] = fields(&header, line)?;
+    if p != "p" { return Err(ParseError::new(line, ParseErrorKind::MissingHeader)); }
+    if kind != problem { return Err(ParseError::new(line, ParseErrorKind::WrongProblem(kind.to_string()))); }
+    let (nvert, narcs) = (vertex_count(nvert, line)?, number(narcs, line)?);
+    let rest: Lines = lines.collect();
+    if let Some((line, _)) = rest.iter().find(|(_, tokens)| tokens[0] == "p") {
+        return Err(ParseError::new(*line, ParseErrorKind::DuplicateHeader));
+    }
+    Ok((nvert, narcs, rest))
+}
+
+type Edge<T> = (usize, usize, T);
+
+// NOTE: entries tagged with the side that listed them, and the last line that listed their pair
+type Waiting<T> = (Vec<(Ordering, T)>, usize);
+
+// NOTE: the edges of an adjacency list text in insertion order, so the lists of a directed graph
+// come out as written. Undirected edges are returned once, from the side with the smaller vertex,
+// and every entry needs a partner with the same weight on the other side
+fn parse_adjacency_list<T: Copy + PartialEq>(
+    text: &str,
+    directed: bool,
+    mut entry: impl FnMut(&str, usize, usize) -> Result<(usize, T), ParseError>
+) -> Result<(usize, Vec<Edge<T>>), ParseError> {
+    let mut lines = content_lines(text, "#");
+    let (line, header) = lines.next().ok_or_else(|| ParseError::new(last_line(text), ParseErrorKind::MissingHeader))?;
+    let [nvert] = fields(&header, line)?;
+    let nvert = vertex_count(nvert, line)?;
+    let mut edges = Vec::new();
+    // NOTE: for undirected graphs, the entries of each pair still waiting for a partner
+    let mut unmatched: HashMap<(usize, usize), Waiting<T>> = HashMap::new();
+    for (line, tokens) in lines {
+        let u = match tokens[0].strip_suffix(':') {
+            Some(u) => vertex(u, line, nvert, 0)?,
+            None => return Err(ParseError::new(line, ParseErrorKind::Malformed(tokens.join(" ")))),
+        };
+        let mut row = Vec::with_capacity(tokens.len() - 1);
+        for token in &tokens[1..] {
+            let (v, weight) = entry(token, line, nvert)?;
+            if !directed {
+                let (waiting, last) = unmatched.entry((u.min(v), u.max(v))).or_insert((Vec::new(), line));
+                *last = line;
+                // NOTE: a self-loop is its own other side, so its second copy pairs up with the first
+                let side = u.cmp(&v);
+                let partner = waiting.iter().position(|&(other, other_weight)| other == side.reverse() && other_weight == weight);
+                match partner {
+                    Some(idx) => { waiting.swap_remove(idx); },
+                    None => waiting.push((side, weight)),
+                }
+                if u > v || (u == v && partner.is_some()) { continue; }
+            }
+            row.push((u, v, weight));
+        }
+        // NOTE: edges are prepended to the adjacency lists
+        row.reverse();
+        edges.extend(row);
+    }
+    let mismatch = unmatched.into_iter()
+        .filter(|(_, (waiting, _))| !waiting.is_empty())
+        .min_by_key(|&(_, (_, line))| line);
+    match mismatch {
+        Some(((u, v), (_, line))) => Err(ParseError::new(line, ParseErrorKind::Asymmetric(u, v))),
+        None => Ok((nvert, edges)),
+    }
+}
+
+// NOTE: every edge once, in adjacency order within each vertex but reversed, so that a file
+// written from a list graph inserts the edges of each vertex in their original order. An
+// undirected edge is kept at its smaller endpoint, and only every other copy of a self-loop (which
+// the list graphs store twice)
+fn edges_once<G: GraphLike>(graph: &G) -> Vec<(usize, usize, G::Weight)> {
+    let mut edges = Vec::new();
+    for u in graph.vertices() {
+        let mut row = Vec::new();
+        let mut loops = 0;
+        for (v, weight) in graph.weighted_neighbors(u) {
+            if u == v && !graph.is_directed() {
+                if loops % 2 == 0 { row.push((u, v, weight)); }
+                loops += 1;
+            } else if graph.is_directed() || u < v {
+                row.push((u, v, weight));
+            }
+        }
+        row.reverse();
+        edges.extend(row);
+    }
+    edges
+}
+
+fn dot_attributes(attributes: &[String]) -> String {
+    if attributes.is_empty() { String::new() } else { format!(" [{}]", attributes.join(", ")) }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingHeader => write!(f, "missing header line"),
+            ParseErrorKind::DuplicateHeader => write!(f, "a second header line"),
+            ParseErrorKind::Malformed(content) => write!(f, "malformed line `{}`", content),
+            ParseErrorKind::InvalidNumber(token) => write!(f, "`{}` is not a valid number", token),
+            ParseErrorKind::VertexOutOfRange(v) => write!(f, "vertex {} is out of range", v),
+            ParseErrorKind::TooManyVertices(nvert) => write!(f, "{} vertices exceed the limit of {}", nvert, MAX_VERTICES),
+            ParseErrorKind::EdgeCount { expected, found } => write!(f, "header announces {} edges, found {}", expected, found),
+            ParseErrorKind::WrongProblem(problem) => write!(f, "unexpected problem type `{}`", problem),
+            ParseErrorKind::NegativeCapacity(capacity) => write!(f, "negative capacity {}", capacity),
+            ParseErrorKind::MissingTerminal(terminal) => write!(f, "no {} given", terminal),
+            ParseErrorKind::SourceIsSink(v) => write!(f, "vertex {} is both the source and the sink", v),
+            ParseErrorKind::Asymmetric(u, v) => write!(f, "undirected edge {} - {} is not listed at both endpoints", u, v),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for HighlightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HighlightError::ComponentCount { expected, found } => write!(f, "component coloring has {} entries for {} vertices", found, expected),
+        }
+    }
+}
+
+impl std::error::Error for HighlightError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    fn sorted_edges<G: GraphLike<Weight = i32>>(graph: &G) -> Vec<(usize, usize, i32)> {
+        let mut edges = edges_once(graph);
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn edge_list() {
+        let text = "# a triangle with a tail\n4 4\n0 1\n\n1 2\n2 0\n2 3\n";
+        let graph = read_edge_list(text, false).unwrap();
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (4, 4));
+        assert!(graph.has_edge(3, 2) && graph.has_edge(0, 2));
+        assert_eq!(write_edge_list(&graph), "4 4\n0 1\n0 2\n1 2\n2 3\n");
+
+        let graph: WeightedGraph<f64> = read_weighted_edge_list("3 2\n0 1 2.5\n1 2 -1\n", true).unwrap();
+        assert_eq!(graph.weighted_neighbors(1).collect::<Vec<_>>(), vec![(2, -1.0)]);
+        assert_eq!(write_weighted_edge_list(&graph), "3 2\n0 1 2.5\n1 2 -1\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |result: Result<Graph, ParseError>| {
+            let error = result.err().expect("input is malformed");
+            (error.line(), error.kind)
+        };
+        assert_eq!(error(read_edge_list("", true)), (1, ParseErrorKind::MissingHeader));
+        assert_eq!(error(read_edge_list("3 2\n0 1\n\n0 x\n", true)), (4, ParseErrorKind::InvalidNumber(String::from("x"))));
+        assert_eq!(error(read_edge_list("3 1\n0 3\n", true)), (2, ParseErrorKind::VertexOutOfRange(3)));
+        assert_eq!(error(read_edge_list("3 2\n0 1\n# done\n", true)), (3, ParseErrorKind::EdgeCount { expected: 2, found: 1 }));
+        assert_eq!(error(read_edge_list("3 1\n0 1 7\n", true)), (2, ParseErrorKind::Malformed(String::from("0 1 7"))));
+        assert_eq!(error(read_adjacency_list("2\n0: 1\n", false)), (2, ParseErrorKind::Asymmetric(0, 1)));
+        assert_eq!(error(read_adjacency_list("2\n1: 1 0\n0: 1\n", false)), (2, ParseErrorKind::Asymmetric(1, 1)));
+        assert_eq!(error(read_adjacency_list("2\n0 1\n", true)), (2, ParseErrorKind::Malformed(String::from("0 1"))));
+        let huge = usize::MAX;
+        assert_eq!(error(read_edge_list(&format!("{} 0\n", huge), true)), (1, ParseErrorKind::TooManyVertices(huge)));
+        assert_eq!(error(read_adjacency_list(&format!("# huge\n{}\n", huge), true)), (2, ParseErrorKind::TooManyVertices(huge)));
+        let huge = MAX_VERTICES + 1;
+        assert_eq!(error(read_edge_list(&format!("{} 0\n", huge), true)), (1, ParseErrorKind::TooManyVertices(huge)));
+
+        let error = read_weighted_adjacency_list::<i32>("2\n0: 1[w:x]\n", true).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: `x` is not a valid number");
+        let error = read_weighted_adjacency_list::<i32>("2\n0: 1[w:2]\n1: 0[w:5]\n", false).unwrap_err();
+        assert_eq!((error.line(), error.kind()), (3, &ParseErrorKind::Asymmetric(0, 1)));
+        let error = read_weighted_adjacency_list::<i32>("1\n0: 0[w:2] 0[w:3]\n", false).unwrap_err();
+        assert_eq!((error.line(), error.kind()), (2, &ParseErrorKind::Asymmetric(0, 0)));
+        let graph: WeightedGraph = read_weighted_adjacency_list("2\n0: 1[w:2] 1[w:5]\n1: 0[w:5] 0[w:2]\n", false).unwrap();
+        assert_eq!(sorted_edges(&graph), vec![(0, 1, 2), (0, 1, 5)]);
+        let error = read_dimacs_graph::<i32>("c\np max 2 1\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: unexpected problem type `max`");
+        let error = read_dimacs_graph::<i32>("p sp 2 1\na 1 2 3\np sp 2 1\n").unwrap_err();
+        assert_eq!((error.line(), error.kind()), (3, &ParseErrorKind::DuplicateHeader));
+        let error = read_dimacs_graph::<i32>("p sp 2 1\na 0 2 3\n").unwrap_err();
+        assert_eq!((error.line(), error.kind()), (2, &ParseErrorKind::VertexOutOfRange(0)));
+        let error = read_dimacs_flow("p max 2 1\nn 1 s\na 1 2 3\n").unwrap_err();
+        assert_eq!((error.line(), error.kind()), (3, &ParseErrorKind::MissingTerminal("sink")));
+        let error = read_dimacs_flow("p max 2 1\nn 1 s\nn 2 t\na 1 2 -3\n").unwrap_err();
+        assert_eq!((error.line(), error.kind()), (4, &ParseErrorKind::NegativeCapacity(-3)));
+        let error = read_dimacs_flow("p max 2 0\nn 1 t\nn 1 s\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: vertex 1 is both the source and the sink");
+        let error = read_dimacs_graph::<i32>(&format!("p sp {} 0\n", huge)).unwrap_err();
+        assert_eq!((error.line(), error.kind()), (1, &ParseErrorKind::TooManyVertices(huge)));
+        let error = read_dimacs_flow(&format!("c\np max {} 0\n", huge)).unwrap_err();
+        assert_eq!(error.to_string(), format!("line 2: {} vertices exceed the limit of {}", huge, MAX_VERTICES));
+    }
+
+    #[test]
+    fn dimacs() {
+        let text = "c a small road network\np sp 4 5\na 1 2 4\na 1 3 1\na 3 2 2\nc\na 2 4 5\na 3 4 8\n";
+        let graph: WeightedGraph = read_dimacs_graph(text).unwrap();
+        let paths = graph.dijkstras(0).unwrap();
+        assert_eq!(paths.path_to(3).unwrap().vertices(), &[0, 2, 1, 3]);
+        assert_eq!(paths.distance_to(3), Some(8));
+        let written = write_dimacs_graph(&graph);
+        assert_eq!(written, "p sp 4 5\na 1 2 4\na 1 3 1\na 2 4 5\na 3 2 2\na 3 4 8\n");
+        assert_eq!(sorted_edges(&read_dimacs_graph::<i32>(&written).unwrap()), sorted_edges(&graph));
+
+        let mut undirected = WeightedGraph::new(2, false);
+        undirected.insert_edge(0, 1, 3);
+        assert_eq!(write_dimacs_graph(&undirected), "p sp 2 2\na 1 2 3\na 2 1 3\n");
+    }
+
+    #[test]
+    fn dimacs_flow() {
+        // NOTE: the example from the DIMACS max-flow format description
+        let text = "c example\np max 6 8\nn 1 s\nn 6 t\na 1 2 5\na 1 3 15\na 2 4 5\na 2 5 5\na 3 4 5\na 3 5 5\na 4 6 15\na 5 6 5\n";
+        let (network, source, sink) = read_dimacs_flow(text).unwrap();
+        assert_eq!((source, sink), (0, 5));
+        assert_eq!(network.max_flow(source, sink).value(), 15);
+        let written = write_dimacs_flow(&network, source, sink);
+        assert_eq!(written, text.replacen("c example\n", "", 1));
+        let (network, source, sink) = read_dimacs_flow(&written).unwrap();
+        assert_eq!(network.max_flow(source, sink).value(), 15);
+    }
+
+    #[test]
+    fn adjacency_list() {
+        let text = "4\n0: 2 1\n2: 3 0 3\n";
+        let graph = read_adjacency_list(text, true).unwrap();
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![3, 0, 3]);
+        assert_eq!(write_adjacency_list(&graph), "4\n0: 2 1\n1:\n2: 3 0 3\n3:\n");
+
+        let mut graph = WeightedGraph::new(3, false);
+        graph.insert_edge(0, 1, 2);
+        graph.insert_edge(1, 1, 5);
+        graph.insert_edge(2, 1, 4);
+        let written = write_weighted_adjacency_list(&graph);
+        assert_eq!(written, "3\n0: 1[w:2]\n1: 2[w:4] 1[w:5] 1[w:5] 0[w:2]\n2: 1[w:4]\n");
+        let read: WeightedGraph = read_weighted_adjacency_list(&written, false).unwrap();
+        assert_eq!((read.edge_count(), read.degree(1)), (3, 4));
+        assert_eq!(sorted_edges(&read), sorted_edges(&graph));
+    }
+
+    #[test]
+    fn dot() {
+        let mut graph = WeightedGraph::new(3, false);
+        graph.insert_edge(0, 1, 2);
+        graph.insert_edge(1, 2, 3);
+        graph.insert_edge(0, 2, 9);
+        assert_eq!(write_dot(&graph, false, None).unwrap(), "graph {\n    0;\n    1;\n    2;\n    0 -- 1;\n    0 -- 2;\n    1 -- 2;\n}\n");
+
+        let path = graph.dijkstras(0).unwrap().path_to(2).unwrap();
+        let dot = write_dot(&graph, true, Some(Highlight::Path(&path))).unwrap();
+        assert!(dot.contains("    2 [color=red];\n"));
+        assert!(dot.contains("    1 -- 2 [label=\"3\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    0 -- 2 [label=\"9\"];\n"));
+
+        let tree = graph.kruskals().unwrap();
+        let dot = write_dot(&graph, false, Some(Highlight::Tree(&tree))).unwrap();
+        assert_eq!(dot.matches("penwidth").count(), 2);
+        assert!(!dot.contains("0 -- 2 ["));
+
+        let mut directed = Graph::new(3, true);
+        directed.insert_edge(0, 1);
+        directed.insert_edge(1, 0);
+        let component = directed.tarjans_scc();
+        let dot = write_dot(&directed, false, Some(Highlight::Components(&component))).unwrap();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    0 -> 1;\n"));
+        assert_eq!(dot.matches(&format!("fillcolor=\"/set312/{}\"", component[0] + 1)).count(), 2);
+        let error = write_dot(&directed, false, Some(Highlight::Components(&component[..2]))).unwrap_err();
+        assert_eq!(error, HighlightError::ComponentCount { expected: 3, found: 2 });
+        assert_eq!(error.to_string(), "component coloring has 2 entries for 3 vertices");
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut rng = XorShift(0x1f83d9abfb41bd6b);
+        for directed in [true, false] {
+            for _ in 0..20 {
+                let nvert = 1 + rng.below(15);
+                let nedges = rng.below(nvert * 3);
+                let graph = rng.weighted_graph(nvert, nedges, directed, -5..20);
+                let expected = sorted_edges(&graph);
+                let from_edge_list: WeightedGraph = read_weighted_edge_list(&write_weighted_edge_list(&graph), directed).unwrap();
+                let from_adjacency: WeightedGraph = read_weighted_adjacency_list(&write_weighted_adjacency_list(&graph), directed).unwrap();
+                assert_eq!(sorted_edges(&from_edge_list), expected);
+                assert_eq!(sorted_edges(&from_adjacency), expected);
+                assert_eq!(from_edge_list.edge_count(), graph.edge_count());
+                if directed {
+                    // NOTE: the lists of a directed graph come back in the same order
+                    assert_eq!(write_weighted_adjacency_list(&from_edge_list), write_weighted_adjacency_list(&graph));
+                    assert_eq!(write_weighted_adjacency_list(&from_adjacency), write_weighted_adjacency_list(&graph));
+                    let from_dimacs: WeightedGraph = read_dimacs_graph(&write_dimacs_graph(&graph)).unwrap();
+                    assert_eq!(write_weighted_adjacency_list(&from_dimacs), write_weighted_adjacency_list(&graph));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,604 @@
+use std::{
+    fmt,
+    iter,
+    ops::Range,
+    cmp::Reverse
+};
+
+use crate::containers::priority_queue::heap;
+use crate::graphs::all_pairs::{self, AllPairsPaths};
+use crate::graphs::connectivity;
+use crate::graphs::dag::{self, CriticalPath, DagError};
+use crate::graphs::k_shortest;
+use crate::graphs::search::{BreadthFirstSearcher, DepthFirstSearcher, BfsEvents, DfsEvents, SearchEvent, Control, EdgeKind, OnEdge};
+use crate::graphs::unweighted::{self, Graph};
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::{self, WeightedGraph, ShortestPaths, AStarSearch, PathError, Path};
+
+// NOTE: the vertices of a cycle in order, the last one having an edge back to the first
+#[derive(Debug, PartialEq)]
+pub struct Cycle(pub(crate) Vec<usize>);
+
+// NOTE: the two sides of a bipartite graph, each sorted
+pub type Bipartition = (Vec<usize>, Vec<usize>);
+
+#[derive(Debug, PartialEq)]
+pub enum TopologicalError {
+    Undirected,
+    Cycle(Cycle),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TwoColorError {
+    Directed,
+    OddCycle(Cycle),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BiconnectivityError {
+    Directed,
+}
+
+// NOTE: the adjacency structure shared by every graph type, and every algorithm that only needs
+// that much. Undirected edges show up as neighbors of both endpoints; unweighted graphs report
+// every edge with weight 1
+pub trait GraphLike: Sized {
+    type Weight: Weight;
+    type Neighbors<'a>: Iterator<Item = usize> where Self: 'a;
+    type WeightedNeighbors<'a>: Iterator<Item = (usize, Self::Weight)> where Self: 'a;
+
+    fn vertices(&self) -> Range<usize>;
+    fn is_directed(&self) -> bool;
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_>;
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_>;
+
+    fn init_bfs(&self) -> BreadthFirstSearcher<'_, Self> {
+        BreadthFirstSearcher::new(self)
+    }
+
+    fn init_dfs(&self) -> DepthFirstSearcher<'_, Self> {
+        DepthFirstSearcher::new(self)
+    }
+
+    // NOTE: the vertices reachable from `start`, lazily in breadth-first (depth-first) discovery
+    // order. The `_events` variants also yield the classified edges and finished vertices
+    fn bfs_iter(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        self.bfs_events(start).filter_map(SearchEvent::discovered)
+    }
+
+    fn dfs_iter(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        self.dfs_events(start).filter_map(SearchEvent::discovered)
+    }
+
+    fn bfs_events(&self, start: usize) -> BfsEvents<'_, Self> {
+        BfsEvents::new(self, start)
+    }
+
+    fn dfs_events(&self, start: usize) -> DfsEvents<'_, Self> {
+        DfsEvents::new(self, start)
+    }
+
+    fn connected_components(&self) -> usize {
+        let mut bfs = self.init_bfs();
+        let mut cc = 0;
+        for i in self.vertices() {
+            if !bfs.is_discovered(i) {
+                cc += 1;
+                bfs.search_from(i, &mut ());
+            }
+        }
+        cc
+    }
+
+    fn find_path(&self, start: usize, end: usize) -> Option<Vec<usize>> {
+        let mut builder = Vec::new();
+        let mut bfs = self.init_bfs();
+        bfs.search_from(start, &mut ());
+        let mut cur_vertex = end;
+        builder.insert(0, cur_vertex);
+        while let Some(parent) = bfs.parent(cur_vertex) {
+            builder.insert(0, parent);
+            if parent == start { return Some(builder) }
+            cur_vertex = parent;
+        }
+        None
+    }
+
+    fn find_cycle(&self, start: usize) -> Option<Vec<usize>> {
+        find_cycle_from(&mut self.init_dfs(), start)
+    }
+
+    // NOTE: vertices sorted by decreasing DFS exit time, which puts every vertex before all the
+    // vertices it has an edge to. A back edge is the only thing that can break this, and it means
+    // there is a cycle
+    fn topological_sort(&self) -> Result<Vec<usize>, TopologicalError> {
+        if !self.is_directed() { return Err(TopologicalError::Undirected); }
+        let mut dfs = self.init_dfs();
+        for v in self.vertices() {
+            if dfs.is_discovered(v) { continue; }
+            if let Some(cycle) = find_cycle_from(&mut dfs, v) { return Err(TopologicalError::Cycle(Cycle(cycle))); }
+        }
+        let mut order: Vec<usize> = self.vertices().collect();
+        order.sort_by_key(|&v| Reverse(dfs.exit_time(v)));
+        Ok(order)
+    }
+
+    // NOTE: Kahn's algorithm, repeatedly taking the smallest vertex without remaining incoming
+    // edges, which gives the lexicographically smallest of all topological orders
+    fn lexicographic_topological_sort(&self) -> Result<Vec<usize>, TopologicalError> {
+        if !self.is_directed() { return Err(TopologicalError::Undirected); }
+        let nvert = self.vertices().len();
+        let mut in_degree = vec![0; nvert];
+        self.vertices().for_each(|u| self.neighbors(u).for_each(|v| in_degree[v] += 1));
+        let mut queue: heap::Heap<usize> = heap::Heap::new();
+        (0..nvert).filter(|&v| in_degree[v] == 0).for_each(|v| queue.insert(v));
+        let mut order = Vec::with_capacity(nvert);
+        while let Some(u) = queue.pop() {
+            order.push(u);
+            self.neighbors(u).for_each(|v| {
+                in_degree[v] -= 1;
+                if in_degree[v] == 0 { queue.insert(v); }
+            });
+        }
+        if order.len() < nvert {
+            return Err(self.topological_sort().expect_err("vertices left over by Kahn's algorithm lie behind a cycle"));
+        }
+        Ok(order)
+    }
+
+    // NOTE: color vertices by the parity of their BFS depth. Non-tree edges of a BFS join vertices
+    // at most one level apart, so the coloring fails exactly on an edge within a level, which
+    // closes an odd cycle through the lowest common ancestor of its endpoints
+    fn two_color(&self) -> Result<Bipartition, TwoColorError> {
+        if self.is_directed() { return Err(TwoColorError::Directed); }
+        let nvert = self.vertices().len();
+        let mut bfs = self.init_bfs();
+        let mut depth: Vec<usize> = vec![0; nvert];
+        for v in self.vertices() {
+            if bfs.is_discovered(v) { continue; }
+            bfs.search_from(v, &mut OnEdge(|from, to, kind| {
+                if kind == EdgeKind::Tree { depth[to] = depth[from] + 1; }
+                Control::Continue
+            }));
+        }
+
+        let conflict = self.vertices().flat_map(|u| self.neighbors(u).map(move |v| (u, v))).find(|&(u, v)| depth[u] == depth[v]);
+        match conflict {
+            None => Ok((0..nvert).partition(|&v| depth[v].is_multiple_of(2))),
+            Some((u, v)) => {
+                let (mut up_u, mut up_v) = (vec![u], vec![v]);
+                let (mut cur_u, mut cur_v) = (u, v);
+                while cur_u != cur_v {
+                    cur_u = bfs.parent(cur_u).expect("both sides meet before the root");
+                    cur_v = bfs.parent(cur_v).expect("both sides meet before the root");
+                    up_u.push(cur_u);
+                    up_v.push(cur_v);
+                }
+                // NOTE: down from the common ancestor to `u`, across to `v` and back up
+                up_v.pop();
+                up_u.reverse();
+                up_u.extend(up_v);
+                Err(TwoColorError::OddCycle(Cycle(up_u)))
+            },
+        }
+    }
+
+    fn dijkstras(&self, start: usize) -> Result<ShortestPaths<'_, Self::Weight, Self>, PathError> {
+        weighted::dijkstras_excluding(self, start, &[], &[])
+    }
+
+    fn bellman_ford(&self, start: usize) -> Result<ShortestPaths<'_, Self::Weight, Self>, PathError> {
+        weighted::bellman_ford(self, start)
+    }
+
+    // NOTE: returns the path to `goal` (if any) along with the number of expanded vertices. The
+    // heuristic must be consistent, i.e. `heuristic(u) <= weight(u, v) + heuristic(v)` for every
+    // edge, which is checked in debug builds
+    fn a_star(
+        &self, 
+        start: usize, 
+        goal: usize, 
+        heuristic: impl Fn(usize) -> Self::Weight
+    ) -> Result<AStarSearch<Self::Weight>, PathError> {
+        weighted::a_star(self, start, goal, heuristic)
+    }
+
+    fn floyd_warshall(&self) -> Result<AllPairsPaths<Self::Weight>, PathError> {
+        all_pairs::floyd_warshall(self)
+    }
+
+    fn johnsons(&self) -> Result<AllPairsPaths<Self::Weight>, PathError> {
+        all_pairs::johnsons(self)
+    }
+
+    // NOTE: the `k` cheapest simple paths from `start` to `end`, cheapest first
+    fn k_shortest_paths(&self, start: usize, end: usize, k: usize) -> Result<Vec<Path<Self::Weight>>, PathError> {
+        k_shortest::k_shortest_paths(self, start, end, k)
+    }
+
+    // NOTE: single-source paths on a directed acyclic graph, relaxing the edges in topological
+    // order in O(V + E). Negative weights are fine
+    fn dag_shortest_paths(&self, start: usize) -> Result<ShortestPaths<'_, Self::Weight, Self>, DagError> {
+        dag::dag_shortest_paths(self, start)
+    }
+
+    fn dag_longest_paths(&self, start: usize) -> Result<ShortestPaths<'_, Self::Weight, Self>, DagError> {
+        dag::dag_longest_paths(self, start)
+    }
+
+    fn critical_path(&self) -> Result<CriticalPath<Self::Weight>, DagError> {
+        dag::critical_path(self)
+    }
+
+    // NOTE: both SCC algorithms number the components in topological order of the condensation,
+    // so every edge between two components goes from the smaller id to the larger one
+    fn tarjans_scc(&self) -> Vec<usize> {
+        connectivity::tarjans_scc(self)
+    }
+
+    fn kosarajus_scc(&self) -> Vec<usize> {
+        connectivity::kosarajus_scc(self)
+    }
+
+    // NOTE: the DAG with one vertex per strongly connected component (numbered as in `tarjans_scc`)
+    // and a single edge wherever some edge joins two components
+    fn condensation(&self) -> Graph {
+        connectivity::condensation(self)
+    }
+
+    // NOTE: cut vertices, bridges and blocks are only defined on undirected graphs; these three
+    // methods return `BiconnectivityError::Directed` when given a directed one
+    fn articulation_points(&self) -> Result<Vec<usize>, BiconnectivityError> {
+        connectivity::articulation_points(self)
+    }
+
+    // NOTE: each bridge as `(u, v)` with `u < v`, sorted
+    fn bridges(&self) -> Result<Vec<(usize, usize)>, BiconnectivityError> {
+        connectivity::bridges(self)
+    }
+
+    // NOTE: the sorted vertex sets of the maximal 2-connected pieces. Blocks are made of edges, so
+    // articulation points appear in several of them and isolated vertices in none
+    fn biconnected_components(&self) -> Result<Vec<Vec<usize>>, BiconnectivityError> {
+        connectivity::biconnected_components(self)
+    }
+}
+
+impl GraphLike for Graph {
+    type Weight = i32;
+    type Neighbors<'a> = unweighted::Incident<'a>;
+    type WeightedNeighbors<'a> = iter::Map<unweighted::Incident<'a>, fn(usize) -> (usize, i32)>;
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.vcapacity()
+    }
+
+    fn is_directed(&self) -> bool {
+        Graph::is_directed(self)
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.incident(v)
+    }
+
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_> {
+        self.incident(v).map(|points_to| (points_to, 1))
+    }
+}
+
+impl<W: Weight> GraphLike for WeightedGraph<W> {
+    type Weight = W;
+    type Neighbors<'a> = iter::Map<weighted::Incident<'a, W>, fn((usize, W)) -> usize> where W: 'a;
+    type WeightedNeighbors<'a> = weighted::Incident<'a, W> where W: 'a;
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.vcapacity()
+    }
+
+    fn is_directed(&self) -> bool {
+        WeightedGraph::is_directed(self)
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.incident(v).map(|(points_to, _)| points_to)
+    }
+
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_> {
+        self.incident(v)
+    }
+}
+
+impl Cycle {
+    pub fn vertices(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+// NOTE: searches from `start` until the first back edge, which closes a cycle with the tree path
+// leading to it
+fn find_cycle_from<G: GraphLike>(dfs: &mut DepthFirstSearcher<'_, G>, start: usize) -> Option<Vec<usize>> {
+    let mut back_edge = None;
+    dfs.search_from(start, &mut OnEdge(|from, to, kind| {
+        if kind != EdgeKind::Back { return Control::Continue; }
+        back_edge = Some((from, to));
+        Control::Stop
+    }));
+    let (from, to) = back_edge?;
+    let mut cycle = vec![from];
+    let mut cur_vertex = from;
+    while cur_vertex != to {
+        cur_vertex = dfs.parent(cur_vertex).expect("back edges lead to an ancestor");
+        cycle.push(cur_vertex);
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle:")?;
+        self.0.iter().try_for_each(|v| write!(f, " {}", v))
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+impl fmt::Display for TopologicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopologicalError::Undirected => write!(f, "topological sorting needs a directed graph"),
+            TopologicalError::Cycle(cycle) => write!(f, "{}", cycle),
+        }
+    }
+}
+
+impl std::error::Error for TopologicalError {}
+
+impl fmt::Display for TwoColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TwoColorError::Directed => write!(f, "two-coloring needs an undirected graph"),
+            TwoColorError::OddCycle(cycle) => write!(f, "odd {}", cycle),
+        }
+    }
+}
+
+impl std::error::Error for TwoColorError {}
+
+impl fmt::Display for BiconnectivityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BiconnectivityError::Directed => write!(f, "cut vertices and bridges need an undirected graph"),
+        }
+    }
+}
+
+impl std::error::Error for BiconnectivityError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    #[test]
+    fn find_path() {
+        let mut graph = Graph::new(5, true);
+        graph.insert_edge(0, 1);
+         
+        graph.insert_edge(1, 2);    
+        graph.insert_edge(1, 3);
+
+        graph.insert_edge(3, 4);
+        graph.insert_edge(3, 0);
+
+        assert_eq!(Some(vec![0, 1, 3, 4]), graph.find_path(0, 4));
+        assert_eq!(None, graph.find_path(2, 4));
+    }
+
+    #[test]
+    fn connected_components() {
+        let mut graph = Graph::new(5, true);
+        graph.insert_edge(0, 1);
+         
+        graph.insert_edge(1, 2);
+
+        graph.insert_edge(3, 4);
+
+        assert_eq!(2, graph.connected_components());
+
+        let graph = Graph::new(5, true);
+        assert_eq!(5, graph.connected_components());
+
+        let mut graph = Graph::new(8, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(2, 3);
+        graph.insert_edge(4, 5);
+        graph.insert_edge(6, 7);
+        assert_eq!(4, graph.connected_components());
+
+        let mut graph = Graph::new(5, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 3);
+        graph.insert_edge(3, 4);
+        assert_eq!(1, graph.connected_components());
+    }
+
+    #[test]
+    fn dfs() {
+        let mut graph = Graph::new(5, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(4, 1);
+         
+        graph.insert_edge(1, 2);    
+        graph.insert_edge(1, 3);
+
+        graph.insert_edge(3, 4);
+        graph.insert_edge(3, 0);
+        let cycle = graph.find_cycle(0).unwrap();
+        assert_cycle(&graph, &cycle);
+    }
+
+    fn assert_cycle(graph: &Graph, cycle: &[usize]) {
+        let mut vertices = cycle.to_vec();
+        vertices.sort();
+        vertices.dedup();
+        assert_eq!(vertices.len(), cycle.len(), "{:?} repeats a vertex", cycle);
+        (0..cycle.len()).for_each(|i| assert!(graph.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]), "{:?} is not a cycle", cycle));
+    }
+
+    fn assert_topological(graph: &Graph, order: &[usize]) {
+        let mut position = vec![usize::MAX; graph.vertex_count()];
+        order.iter().enumerate().for_each(|(idx, &v)| position[v] = idx);
+        assert!(position.iter().all(|&p| p != usize::MAX));
+        graph.vertices().for_each(|u| graph.vertices().for_each(|v| {
+            if graph.has_edge(u, v) { assert!(position[u] < position[v], "{} -> {} out of order", u, v); }
+        }));
+    }
+
+    #[test]
+    fn find_cycle() {
+        let mut graph = Graph::new(5, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(0, 2);
+        graph.insert_edge(3, 4);
+        assert_eq!(graph.find_cycle(0), None);
+        graph.insert_edge(4, 3);
+        assert_eq!(graph.find_cycle(0), None);
+        assert_cycle(&graph, &graph.find_cycle(3).unwrap());
+        graph.insert_edge(2, 2);
+        assert_eq!(graph.find_cycle(0), Some(vec![2]));
+
+        // NOTE: walking back along a tree edge of an undirected graph is not a cycle
+        let mut graph = Graph::new(5, false);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 3);
+        graph.insert_edge(1, 4);
+        assert_eq!(graph.find_cycle(0), None);
+        graph.insert_edge(3, 1);
+        assert_cycle(&graph, &graph.find_cycle(0).unwrap());
+        assert_eq!(graph.find_cycle(4).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn topological_sort() {
+        // NOTE: getting dressed, from ADM / CLRS
+        let mut graph = Graph::new(8, true);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(0, 3);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(1, 3);
+        graph.insert_edge(4, 3);
+        graph.insert_edge(5, 6);
+        graph.insert_edge(5, 2);
+        graph.insert_edge(6, 7);
+        graph.insert_edge(7, 2);
+        assert_topological(&graph, &graph.topological_sort().unwrap());
+        let order = graph.lexicographic_topological_sort().unwrap();
+        assert_eq!(order, vec![0, 1, 4, 3, 5, 6, 7, 2]);
+
+        graph.insert_edge(2, 5);
+        let Err(TopologicalError::Cycle(cycle)) = graph.topological_sort() else { panic!("2 -> 5 closes a cycle") };
+        assert_cycle(&graph, cycle.vertices());
+        let error = graph.lexicographic_topological_sort().unwrap_err();
+        let TopologicalError::Cycle(cycle) = &error else { panic!("2 -> 5 closes a cycle") };
+        assert_cycle(&graph, cycle.vertices());
+        assert_eq!(error.to_string(), format!("cycle: {}", cycle.vertices().iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" ")));
+
+        let graph = Graph::new(2, false);
+        assert_eq!(graph.topological_sort(), Err(TopologicalError::Undirected));
+        assert_eq!(graph.lexicographic_topological_sort(), Err(TopologicalError::Undirected));
+    }
+
+    #[test]
+    fn two_color() {
+        let mut graph = Graph::new(6, false);
+        (0..6).for_each(|v| graph.insert_edge(v, (v + 1) % 6));
+        assert_eq!(graph.two_color(), Ok((vec![0, 2, 4], vec![1, 3, 5])));
+
+        graph.insert_edge(0, 3);
+        assert!(graph.two_color().is_ok());
+        graph.insert_edge(1, 4);
+        graph.insert_edge(0, 2);
+        let Err(TwoColorError::OddCycle(cycle)) = graph.two_color() else { panic!("0 - 1 - 2 is a triangle") };
+        assert_cycle(&graph, cycle.vertices());
+        assert_eq!(cycle.vertices().len() % 2, 1);
+
+        let mut graph = Graph::new(3, false);
+        graph.insert_edge(2, 2);
+        assert_eq!(graph.two_color(), Err(TwoColorError::OddCycle(Cycle(vec![2]))));
+        assert_eq!(graph.two_color().unwrap_err().to_string(), "odd cycle: 2");
+        assert_eq!(Graph::new(3, false).two_color(), Ok((vec![0, 1, 2], vec![])));
+        assert_eq!(Graph::new(3, true).two_color(), Err(TwoColorError::Directed));
+    }
+
+    #[test]
+    fn two_color_random() {
+        let mut rng = XorShift(0x6a09e667f3bcc908);
+        for _ in 0..50 {
+            let nvert = 1 + rng.below(20);
+            let mut graph = Graph::new(nvert, false);
+            (0..rng.below(nvert * 2)).for_each(|_| graph.insert_edge(rng.below(nvert), rng.below(nvert)));
+            match graph.two_color() {
+                Ok((left, right)) => {
+                    assert_eq!(left.len() + right.len(), nvert);
+                    left.iter().for_each(|&u| left.iter().for_each(|&v| assert!(!graph.has_edge(u, v))));
+                    right.iter().for_each(|&u| right.iter().for_each(|&v| assert!(!graph.has_edge(u, v))));
+                },
+                Err(TwoColorError::OddCycle(cycle)) => {
+                    assert_cycle(&graph, cycle.vertices());
+                    assert_eq!(cycle.vertices().len() % 2, 1);
+                },
+                Err(TwoColorError::Directed) => panic!("the graph is undirected"),
+            }
+        }
+    }
+
+    #[test]
+    fn topological_sort_random() {
+        let mut rng = XorShift(0x9e3779b97f4a7c15);
+        for round in 0..40 {
+            let nvert = 1 + rng.below(20);
+            let mut graph = Graph::new(nvert, true);
+            (0..rng.below(nvert * 2)).for_each(|_| {
+                let (u, v) = (rng.below(nvert), rng.below(nvert));
+                // NOTE: only forward edges, except for every other round where a few may close cycles
+                if u < v || (round % 2 == 1 && rng.below(4) == 0) { graph.insert_edge(u, v); }
+            });
+            match (graph.topological_sort(), graph.lexicographic_topological_sort()) {
+                (Ok(order), Ok(lexicographic)) => {
+                    assert_topological(&graph, &order);
+                    assert_topological(&graph, &lexicographic);
+                    assert!(lexicographic <= order);
+                },
+                (Err(TopologicalError::Cycle(cycle)), Err(TopologicalError::Cycle(other))) => {
+                    assert_cycle(&graph, cycle.vertices());
+                    assert_cycle(&graph, other.vertices());
+                },
+                (order, lexicographic) => panic!("disagree: {:?} vs {:?}", order, lexicographic),
+            }
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, 5);
+        graph.insert_edge(1, 2, -1);
+        graph.insert_edge(2, 1, 2);
+        graph.insert_edge(2, 3, 7);
+        assert_eq!(graph.weighted_neighbors(2).collect::<Vec<_>>(), vec![(3, 7), (1, 2)]);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), vec![3, 1]);
+        assert_eq!(graph.find_path(0, 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(graph.tarjans_scc(), vec![0, 1, 1, 2]);
+        assert_eq!(graph.topological_sort(), Err(TopologicalError::Cycle(Cycle(vec![1, 2]))));
+        assert!(graph.remove_edge(2, 1).is_some());
+        assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![0, 1, 2, 3]));
+
+        let mut unweighted = Graph::new(2, false);
+        unweighted.insert_edge(0, 1);
+        assert_eq!(unweighted.weighted_neighbors(1).collect::<Vec<_>>(), vec![(0, 1)]);
+    }
+}
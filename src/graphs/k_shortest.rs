@@ -0,0 +1,191 @@
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::weight::{self, Weight};
+use crate::graphs::weighted::{self, PathError, Path};
+
+// NOTE: Yen's algorithm. Every path found so far is used as a source of deviations: for each
+// of its vertices (the spur) the prefix up to it is kept, the edges other found paths take out
+// of that same prefix are excluded along with the prefix itself, and Dijkstra finds the best
+// way to finish from the spur. The cheapest deviation not yet taken is the next path
+pub(crate) fn k_shortest_paths<W: Weight, G: GraphLike<Weight = W>>(
+    graph: &G,
+    start: usize,
+    end: usize,
+    k: usize
+) -> Result<Vec<Path<W>>, PathError> {
+    let mut found: Vec<Path<W>> = Vec::with_capacity(k);
+    if k == 0 { return Ok(found); }
+    match weighted::dijkstras_excluding(graph, start, &[], &[])?.path_to(end) {
+        None => return Ok(found),
+        Some(path) => found.push(path),
+    }
+
+    let mut candidates: Vec<Path<W>> = Vec::new();
+    let mut excluded_vertices = vec![false; graph.vertices().len()];
+    // NOTE: every excluded edge leaves the spur, so flagging its other end is enough
+    let mut excluded_targets = vec![false; graph.vertices().len()];
+    let mut too_heavy = None;
+    while found.len() < k {
+        let last = &found[found.len() - 1].path;
+        let mut root_weight = W::ZERO;
+        for spur_idx in 0..last.len() - 1 {
+            let spur = last[spur_idx];
+            let root = &last[..=spur_idx];
+            let targets: Vec<usize> = found.iter()
+                .filter(|path| path.path.len() > spur_idx + 1 && &path.path[..=spur_idx] == root)
+                .map(|path| path.path[spur_idx + 1])
+                .collect();
+            targets.iter().for_each(|&v| excluded_targets[v] = true);
+            root[..spur_idx].iter().for_each(|&v| excluded_vertices[v] = true);
+
+            let spur_paths = weighted::dijkstras_excluding(graph, spur, &excluded_vertices, &excluded_targets)?;
+            if let Some(spur_path) = spur_paths.path_to(end) {
+                let mut path = root[..spur_idx].to_vec();
+                path.extend_from_slice(&spur_path.path);
+                // NOTE: a deviation too heavy to weigh only matters once every lighter one is used up
+                match weight::relax(root_weight, spur_path.weight) {
+                    Ok(weight) => {
+                        let is_new = !candidates.iter().chain(found.iter()).any(|other| other.path == path);
+                        if is_new { candidates.push(Path::new(path, weight)); }
+                    },
+                    Err(_) => too_heavy = Some((spur, path[spur_idx + 1])),
+                }
+            }
+
+            root[..spur_idx].iter().for_each(|&v| excluded_vertices[v] = false);
+            targets.iter().for_each(|&v| excluded_targets[v] = false);
+            // NOTE: edge weights are not negative, so a prefix weighs no more than its whole path
+            root_weight = weight::relax(root_weight, lightest_edge(graph, spur, last[spur_idx + 1]))
+                .expect("a prefix weighs no more than its path");
+        }
+
+        let best = candidates.iter().enumerate()
+            .min_by(|(_, a), (_, b)| a.weight.total_cmp(&b.weight).then_with(|| a.path.cmp(&b.path)))
+            .map(|(idx, _)| idx);
+        match (best, too_heavy) {
+            (None, Some((u, v))) => return Err(PathError::Overflow(u, v)),
+            (None, None) => break,
+            (Some(idx), _) => found.push(candidates.swap_remove(idx)),
+        }
+    }
+    Ok(found)
+}
+
+fn lightest_edge<W: Weight, G: GraphLike<Weight = W>>(graph: &G, u: usize, v: usize) -> W {
+    graph.weighted_neighbors(u)
+        .filter(|&(points_to, _)| points_to == v)
+        .map(|(_, weight)| weight)
+        .min_by(|a, b| a.total_cmp(b))
+        .expect("path edges come from the graph")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+    use crate::graphs::weighted::WeightedGraph;
+
+    // NOTE: every simple path from `start` to `end`, weighted by the lightest parallel edge
+    fn all_simple_paths(graph: &WeightedGraph, start: usize, end: usize) -> Vec<i32> {
+        fn extend(graph: &WeightedGraph, path: &mut Vec<usize>, weight: i32, end: usize, weights: &mut Vec<i32>) {
+            let cur_vertex = *path.last().unwrap();
+            if cur_vertex == end {
+                weights.push(weight);
+                return;
+            }
+            let mut next: Vec<usize> = graph.incident(cur_vertex).map(|(v, _)| v).filter(|v| !path.contains(v)).collect();
+            next.sort();
+            next.dedup();
+            for v in next {
+                let edge_weight = lightest_edge(graph, cur_vertex, v);
+                path.push(v);
+                extend(graph, path, weight + edge_weight, end, weights);
+                path.pop();
+            }
+        }
+        let mut weights = Vec::new();
+        extend(graph, &mut vec![start], 0, end, &mut weights);
+        weights.sort();
+        weights
+    }
+
+    fn check(graph: &WeightedGraph, paths: &[Path], start: usize, end: usize) {
+        paths.iter().enumerate().for_each(|(idx, path)| {
+            assert_eq!((path.path[0], *path.path.last().unwrap()), (start, end));
+            let mut vertices = path.path.clone();
+            vertices.sort();
+            vertices.dedup();
+            assert_eq!(vertices.len(), path.path.len(), "{} is not simple", path);
+            assert_eq!(path.weight, path.edges().map(|(u, v)| lightest_edge(graph, u, v)).sum::<i32>());
+            assert!(paths[..idx].iter().all(|other| other.path != path.path));
+        });
+    }
+
+    #[test]
+    fn yen() {
+        // NOTE: the example from Yen's algorithm on Wikipedia, C D E F G H as 0..6
+        let mut graph = WeightedGraph::new(6, true);
+        graph.insert_edge(0, 1, 3);
+        graph.insert_edge(0, 2, 2);
+        graph.insert_edge(1, 3, 4);
+        graph.insert_edge(2, 1, 1);
+        graph.insert_edge(2, 3, 2);
+        graph.insert_edge(2, 4, 3);
+        graph.insert_edge(3, 4, 2);
+        graph.insert_edge(3, 5, 1);
+        graph.insert_edge(4, 5, 2);
+        let paths = graph.k_shortest_paths(0, 5, 3).unwrap();
+        check(&graph, &paths, 0, 5);
+        assert_eq!(paths.iter().map(|path| path.weight()).collect::<Vec<_>>(), vec![5, 7, 8]);
+        assert_eq!(paths[0].vertices(), &[0, 2, 3, 5]);
+        assert_eq!(paths[1].vertices(), &[0, 2, 4, 5]);
+
+        let paths = graph.k_shortest_paths(0, 5, 100).unwrap();
+        assert_eq!(paths.iter().map(|path| path.weight()).collect::<Vec<_>>(), all_simple_paths(&graph, 0, 5));
+        assert!(graph.k_shortest_paths(0, 5, 0).unwrap().is_empty());
+        assert!(graph.k_shortest_paths(5, 0, 3).unwrap().is_empty());
+        assert_eq!(graph.k_shortest_paths(3, 3, 3).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn negative_edge() {
+        let mut graph = WeightedGraph::new(2, true);
+        graph.insert_edge(0, 1, -1);
+        assert!(matches!(graph.k_shortest_paths(0, 1, 2), Err(PathError::NegativeEdge(0, 1))));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, i32::MAX / 2);
+        graph.insert_edge(1, 3, 1);
+        graph.insert_edge(1, 2, i32::MAX / 2 + 5);
+        graph.insert_edge(2, 3, 1);
+        assert_eq!(graph.k_shortest_paths(0, 3, 2).unwrap_err(), PathError::Overflow(1, 2));
+        assert_eq!(graph.k_shortest_paths(1, 3, 2).unwrap().len(), 2);
+
+        // NOTE: here the spur path from 1 fits on its own but not after the root 0 -> 1
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, i32::MAX - 10);
+        graph.insert_edge(1, 3, 1);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(2, 3, 20);
+        assert_eq!(graph.k_shortest_paths(0, 3, 1).unwrap()[0].vertices(), &[0, 1, 3]);
+        assert_eq!(graph.k_shortest_paths(0, 3, 2).unwrap_err(), PathError::Overflow(1, 2));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = XorShift(0x1234fedc5678ba98);
+        for round in 0..40 {
+            let nvert = 2 + rng.below(7);
+            let nedges = rng.below(nvert * 3);
+            let graph = rng.weighted_graph(nvert, nedges, round % 2 == 0, 1..10);
+            let (start, end) = (rng.below(nvert), rng.below(nvert));
+            let k = 1 + rng.below(8);
+            let paths = graph.k_shortest_paths(start, end, k).unwrap();
+            check(&graph, &paths, start, end);
+            let expected = all_simple_paths(&graph, start, end);
+            assert_eq!(paths.iter().map(|path| path.weight()).collect::<Vec<_>>(), expected[..k.min(expected.len())]);
+        }
+    }
+}
@@ -0,0 +1,473 @@
+use std::{
+    fmt,
+    iter,
+    slice,
+    ops::Range
+};
+
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::unweighted::Graph;
+use crate::graphs::weight::Weight;
+use crate::graphs::weighted::WeightedGraph;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// NOTE: an unweighted graph as an adjacency matrix packed into 64-bit words, one padded row per
+// vertex. Edge lookups are O(1) and whole rows combine a word at a time. Matrix graphs are
+// simple: parallel edges collapse into one bit, and an undirected edge sets both `(i, j)` and
+// `(j, i)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitMatrixGraph {
+    bits: Vec<u64>,
+    row_words: usize,
+    nedges: usize,
+    nvert: usize,
+    directed: bool,
+}
+
+// NOTE: a weighted graph as a dense `nvert * nvert` matrix, `None` marking a missing edge.
+// Inserting an edge that is already there replaces its weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixGraph<W: Weight = i32> {
+    weights: Vec<Option<W>>,
+    nedges: usize,
+    nvert: usize,
+    directed: bool,
+}
+
+impl BitMatrixGraph {
+    pub fn new(nvert: usize, directed: bool) -> Self {
+        let row_words = nvert.div_ceil(WORD_BITS);
+        BitMatrixGraph {
+            bits: vec![0; nvert * row_words],
+            row_words,
+            nedges: 0,
+            nvert,
+            directed,
+        }
+    }
+
+    pub fn insert_edge(&mut self, i: usize, j: usize) {
+        assert!(i < self.nvert && j < self.nvert, "vertices `i` and `j` must be within capacity");
+        if self.has_edge(i, j) { return; }
+        self.nedges += 1;
+        self.set(i, j, true);
+        if !self.directed { self.set(j, i, true); }
+    }
+
+    pub fn remove_edge(&mut self, i: usize, j: usize) -> bool {
+        assert!(i < self.nvert && j < self.nvert, "vertices `i` and `j` must be within capacity");
+        if !self.has_edge(i, j) { return false; }
+        self.nedges -= 1;
+        self.set(i, j, false);
+        if !self.directed { self.set(j, i, false); }
+        true
+    }
+
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.row(i)[j / WORD_BITS] & (1 << (j % WORD_BITS)) != 0
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.nvert
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.nedges
+    }
+
+    // NOTE: an undirected matrix is symmetric, so it is its own transpose
+    pub fn transpose(&self) -> BitMatrixGraph {
+        let mut transposed = BitMatrixGraph::new(self.nvert, self.directed);
+        (0..self.nvert).for_each(|i| self.neighbors(i).for_each(|j| transposed.set(j, i, true)));
+        transposed.nedges = self.nedges;
+        transposed
+    }
+
+    // NOTE: the boolean product, which has an edge `i -> j` exactly when some `k` has `i -> k` in
+    // `self` and `k -> j` in `other`. Row `i` of the product is the union of the rows of `other`
+    // picked out by row `i` of `self`. The product of two symmetric matrices need not be symmetric,
+    // so the result is directed
+    pub fn product(&self, other: &BitMatrixGraph) -> BitMatrixGraph {
+        assert_eq!(self.nvert, other.nvert, "both matrices must have the same number of vertices");
+        let mut product = BitMatrixGraph::new(self.nvert, true);
+        for i in 0..self.nvert {
+            for k in self.neighbors(i) {
+                let (start, end) = (i * self.row_words, (i + 1) * self.row_words);
+                product.bits[start..end].iter_mut().zip(other.row(k)).for_each(|(word, &other_word)| *word |= other_word);
+            }
+        }
+        product.nedges = product.bits.iter().map(|word| word.count_ones() as usize).sum();
+        product
+    }
+
+    // NOTE: Warshall's algorithm on whole rows: once `k` has been allowed as an intermediate
+    // vertex, every `i` reaching `k` also reaches everything `k` reaches. `i -> i` is only set
+    // when `i` lies on a cycle
+    pub fn transitive_closure(&self) -> BitMatrixGraph {
+        let mut closure = self.clone();
+        for k in 0..self.nvert {
+            let row_k = closure.row(k).to_vec();
+            for i in 0..self.nvert {
+                if !closure.has_edge(i, k) { continue; }
+                let (start, end) = (i * self.row_words, (i + 1) * self.row_words);
+                closure.bits[start..end].iter_mut().zip(&row_k).for_each(|(word, &k_word)| *word |= k_word);
+            }
+        }
+        let ones: usize = closure.bits.iter().map(|word| word.count_ones() as usize).sum();
+        let loops = (0..self.nvert).filter(|&v| closure.has_edge(v, v)).count();
+        closure.nedges = if self.directed { ones } else { (ones + loops) / 2 };
+        closure
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.bits[i * self.row_words..(i + 1) * self.row_words]
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: bool) {
+        let word = &mut self.bits[i * self.row_words + j / WORD_BITS];
+        if value { *word |= 1 << (j % WORD_BITS) } else { *word &= !(1 << (j % WORD_BITS)) }
+    }
+}
+
+impl<W: Weight> MatrixGraph<W> {
+    pub fn new(nvert: usize, directed: bool) -> Self {
+        MatrixGraph {
+            weights: vec![None; nvert * nvert],
+            nedges: 0,
+            nvert,
+            directed,
+        }
+    }
+
+    pub fn insert_edge(&mut self, i: usize, j: usize, weight: W) {
+        assert!(i < self.nvert && j < self.nvert, "vertices `i` and `j` must be within capacity");
+        if self.weights[i * self.nvert + j].is_none() { self.nedges += 1; }
+        self.weights[i * self.nvert + j] = Some(weight);
+        if !self.directed { self.weights[j * self.nvert + i] = Some(weight); }
+    }
+
+    pub fn remove_edge(&mut self, i: usize, j: usize) -> Option<W> {
+        assert!(i < self.nvert && j < self.nvert, "vertices `i` and `j` must be within capacity");
+        let weight = self.weights[i * self.nvert + j].take()?;
+        if !self.directed { self.weights[j * self.nvert + i] = None; }
+        self.nedges -= 1;
+        Some(weight)
+    }
+
+    pub fn weight(&self, i: usize, j: usize) -> Option<W> {
+        self.weights[i * self.nvert + j]
+    }
+
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.weight(i, j).is_some()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.nvert
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.nedges
+    }
+
+    pub fn transpose(&self) -> MatrixGraph<W> {
+        let mut transposed = MatrixGraph::new(self.nvert, self.directed);
+        (0..self.nvert).for_each(|i| self.weighted_neighbors(i).for_each(|(j, weight)| {
+            transposed.weights[j * self.nvert + i] = Some(weight);
+        }));
+        transposed.nedges = self.nedges;
+        transposed
+    }
+
+    // NOTE: the (min, +) product: `i -> j` weighs the least `w(i, k) + w'(k, j)` over all `k`, so
+    // squaring the matrix of a graph gives the lightest walks of at most two edges once every
+    // vertex has a zero-weight self-loop. Sums that overflow are treated as missing
+    pub fn min_plus_product(&self, other: &MatrixGraph<W>) -> MatrixGraph<W> {
+        assert_eq!(self.nvert, other.nvert, "both matrices must have the same number of vertices");
+        let mut product = MatrixGraph::new(self.nvert, true);
+        for i in 0..self.nvert {
+            for (k, first) in self.weighted_neighbors(i) {
+                for (j, second) in other.weighted_neighbors(k) {
+                    let Some(candidate) = first.checked_add(second) else { continue };
+                    let cell = &mut product.weights[i * self.nvert + j];
+                    if cell.is_none_or(|current| candidate < current) { *cell = Some(candidate); }
+                }
+            }
+        }
+        product.nedges = product.weights.iter().filter(|cell| cell.is_some()).count();
+        product
+    }
+
+    fn row(&self, i: usize) -> &[Option<W>] {
+        &self.weights[i * self.nvert..(i + 1) * self.nvert]
+    }
+}
+
+// NOTE: the neighbors of a vertex of a `BitMatrixGraph`, by increasing id
+pub struct BitRow<'a> {
+    words: &'a [u64],
+    word: usize,
+    cur_bits: u64,
+}
+
+impl<'a> Iterator for BitRow<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur_bits == 0 {
+            self.word += 1;
+            self.cur_bits = *self.words.get(self.word)?;
+        }
+        let bit = self.cur_bits.trailing_zeros() as usize;
+        // NOTE: clears the lowest set bit
+        self.cur_bits &= self.cur_bits - 1;
+        Some(self.word * WORD_BITS + bit)
+    }
+}
+
+// NOTE: the `(points_to, weight)` pairs of a row of a `MatrixGraph`, by increasing id
+pub struct MatrixRow<'a, W: Weight> {
+    cells: iter::Enumerate<slice::Iter<'a, Option<W>>>,
+}
+
+impl<'a, W: Weight> Iterator for MatrixRow<'a, W> {
+    type Item = (usize, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cells.find_map(|(j, weight)| weight.map(|weight| (j, weight)))
+    }
+}
+
+impl GraphLike for BitMatrixGraph {
+    type Weight = i32;
+    type Neighbors<'a> = BitRow<'a>;
+    type WeightedNeighbors<'a> = iter::Map<BitRow<'a>, fn(usize) -> (usize, i32)>;
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.nvert
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        let words = self.row(v);
+        BitRow { words, word: 0, cur_bits: words.first().copied().unwrap_or(0) }
+    }
+
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_> {
+        self.neighbors(v).map(|points_to| (points_to, 1))
+    }
+}
+
+impl<W: Weight> GraphLike for MatrixGraph<W> {
+    type Weight = W;
+    type Neighbors<'a> = iter::Map<MatrixRow<'a, W>, fn((usize, W)) -> usize> where W: 'a;
+    type WeightedNeighbors<'a> = MatrixRow<'a, W> where W: 'a;
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.nvert
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn neighbors(&self, v: usize) -> Self::Neighbors<'_> {
+        self.weighted_neighbors(v).map(|(points_to, _)| points_to)
+    }
+
+    fn weighted_neighbors(&self, v: usize) -> Self::WeightedNeighbors<'_> {
+        MatrixRow { cells: self.row(v).iter().enumerate() }
+    }
+}
+
+impl From<&Graph> for BitMatrixGraph {
+    fn from(graph: &Graph) -> Self {
+        let mut matrix = BitMatrixGraph::new(graph.vertex_count(), graph.is_directed());
+        graph.vertices().for_each(|u| graph.neighbors(u).for_each(|v| matrix.insert_edge(u, v)));
+        matrix
+    }
+}
+
+// NOTE: edges are inserted by decreasing id, so the adjacency lists of a directed graph come out
+// sorted
+impl From<&BitMatrixGraph> for Graph {
+    fn from(matrix: &BitMatrixGraph) -> Self {
+        let mut graph = Graph::new(matrix.nvert, matrix.directed);
+        matrix.vertices().rev().for_each(|u| {
+            let row: Vec<usize> = matrix.neighbors(u).collect();
+            row.into_iter().rev().filter(|&v| matrix.directed || u <= v).for_each(|v| graph.insert_edge(u, v));
+        });
+        graph
+    }
+}
+
+// NOTE: of several parallel edges only the lightest one is kept
+impl<W: Weight> From<&WeightedGraph<W>> for MatrixGraph<W> {
+    fn from(graph: &WeightedGraph<W>) -> Self {
+        let mut matrix = MatrixGraph::new(graph.vertex_count(), graph.is_directed());
+        graph.vertices().for_each(|u| graph.weighted_neighbors(u).for_each(|(v, weight)| {
+            if matrix.weight(u, v).is_none_or(|current| weight < current) { matrix.insert_edge(u, v, weight); }
+        }));
+        matrix
+    }
+}
+
+impl<W: Weight> From<&MatrixGraph<W>> for WeightedGraph<W> {
+    fn from(matrix: &MatrixGraph<W>) -> Self {
+        let mut graph = WeightedGraph::new(matrix.nvert, matrix.directed);
+        matrix.vertices().rev().for_each(|u| {
+            let row: Vec<(usize, W)> = matrix.weighted_neighbors(u).collect();
+            row.into_iter().rev().filter(|&(v, _)| matrix.directed || u <= v).for_each(|(v, weight)| graph.insert_edge(u, v, weight));
+        });
+        graph
+    }
+}
+
+impl fmt::Display for BitMatrixGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.nvert {
+            (0..self.nvert).try_for_each(|j| write!(f, "{}", if self.has_edge(i, j) { '1' } else { '.' }))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Weight> fmt::Display for MatrixGraph<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.nvert {
+            let cells: Vec<String> = self.row(i).iter()
+                .map(|cell| cell.map_or(String::from("-"), |weight| weight.to_string()))
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    #[test]
+    fn bit_matrix() {
+        let mut matrix = BitMatrixGraph::new(70, true);
+        matrix.insert_edge(0, 69);
+        matrix.insert_edge(0, 3);
+        matrix.insert_edge(0, 3);
+        matrix.insert_edge(69, 64);
+        assert_eq!(matrix.edge_count(), 3);
+        assert!(matrix.has_edge(0, 69) && !matrix.has_edge(69, 0));
+        assert_eq!(matrix.neighbors(0).collect::<Vec<_>>(), vec![3, 69]);
+        assert_eq!(matrix.neighbors(69).collect::<Vec<_>>(), vec![64]);
+        assert_eq!(matrix.find_path(0, 64), Some(vec![0, 69, 64]));
+        assert!(matrix.remove_edge(0, 3));
+        assert!(!matrix.remove_edge(0, 3));
+        assert_eq!(matrix.edge_count(), 2);
+
+        let transposed = matrix.transpose();
+        assert!(transposed.has_edge(69, 0) && transposed.has_edge(64, 69));
+        assert_eq!(transposed.transpose(), matrix);
+
+        let mut matrix = BitMatrixGraph::new(3, false);
+        matrix.insert_edge(0, 1);
+        matrix.insert_edge(1, 1);
+        assert!(matrix.has_edge(1, 0));
+        assert_eq!(matrix.edge_count(), 2);
+        assert_eq!(matrix.to_string(), ".1.\n11.\n...\n");
+        assert_eq!(matrix.transpose(), matrix);
+        assert_eq!(BitMatrixGraph::new(0, true).neighbors(0).count(), 0);
+    }
+
+    #[test]
+    fn bit_matrix_random() {
+        let mut rng = XorShift(0x3c6ef372fe94f82b);
+        for directed in [true, false] {
+            for _ in 0..20 {
+                let nvert = 1 + rng.below(100);
+                let mut graph = Graph::new(nvert, directed);
+                (0..rng.below(nvert * 2)).for_each(|_| graph.insert_edge(rng.below(nvert), rng.below(nvert)));
+                let matrix = BitMatrixGraph::from(&graph);
+                assert_eq!(BitMatrixGraph::from(&Graph::from(&matrix)), matrix);
+                graph.vertices().for_each(|u| graph.vertices().for_each(|v| assert_eq!(matrix.has_edge(u, v), graph.has_edge(u, v))));
+                let (matrix_scc, list_scc) = (matrix.tarjans_scc(), graph.tarjans_scc());
+                graph.vertices().for_each(|u| graph.vertices().for_each(|v| {
+                    assert_eq!(matrix_scc[u] == matrix_scc[v], list_scc[u] == list_scc[v]);
+                }));
+
+                // NOTE: the square has `u -> w` exactly for the walks `u -> v -> w`, the closure for
+                // every walk of at least one edge
+                let square = matrix.product(&matrix);
+                let closure = matrix.transitive_closure();
+                graph.vertices().for_each(|u| {
+                    let mut two_steps = vec![false; nvert];
+                    graph.neighbors(u).for_each(|v| graph.neighbors(v).for_each(|w| two_steps[w] = true));
+                    let mut reached = vec![false; nvert];
+                    graph.neighbors(u).for_each(|v| graph.bfs_iter(v).for_each(|w| reached[w] = true));
+                    graph.vertices().for_each(|w| {
+                        assert_eq!(square.has_edge(u, w), two_steps[w]);
+                        assert_eq!(closure.has_edge(u, w), reached[w]);
+                    });
+                });
+                assert_eq!(square.edge_count(), square.vertices().map(|u| square.neighbors(u).count()).sum::<usize>());
+                let ones: usize = closure.vertices().map(|u| closure.neighbors(u).count()).sum();
+                let loops = closure.vertices().filter(|&u| closure.has_edge(u, u)).count();
+                assert_eq!(closure.edge_count(), if directed { ones } else { (ones + loops) / 2 });
+            }
+        }
+    }
+
+    #[test]
+    fn matrix() {
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, 7);
+        graph.insert_edge(0, 1, 3);
+        graph.insert_edge(1, 2, 2);
+        graph.insert_edge(0, 2, 9);
+        graph.insert_edge(2, 3, -1);
+        let mut matrix = MatrixGraph::from(&graph);
+        assert_eq!((matrix.edge_count(), matrix.weight(0, 1), matrix.weight(1, 0)), (4, Some(3), None));
+        assert_eq!(matrix.to_string(), "- 3 9 -\n- - 2 -\n- - - -1\n- - - -\n");
+        assert_eq!(matrix.bellman_ford(0).unwrap().distance, vec![0, 3, 5, 4]);
+        assert_eq!(matrix.transpose().weight(3, 2), Some(-1));
+        assert_eq!(MatrixGraph::from(&WeightedGraph::from(&matrix)), matrix);
+
+        matrix.insert_edge(0, 1, 4);
+        assert_eq!((matrix.edge_count(), matrix.weight(0, 1)), (4, Some(4)));
+        assert_eq!(matrix.remove_edge(0, 2), Some(9));
+        assert_eq!(matrix.remove_edge(0, 2), None);
+        assert_eq!(matrix.edge_count(), 3);
+
+        let mut undirected: MatrixGraph<f64> = MatrixGraph::new(2, false);
+        undirected.insert_edge(1, 0, 0.5);
+        assert_eq!((undirected.weight(0, 1), undirected.edge_count()), (Some(0.5), 1));
+        assert_eq!(undirected.neighbors(0).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn min_plus_random() {
+        let mut rng = XorShift(0xa54ff53a5f1d36f1);
+        for _ in 0..20 {
+            let nvert = 1 + rng.below(12);
+            let nedges = rng.below(nvert * nvert);
+            let mut graph = rng.weighted_graph(nvert, nedges, true, 0..20);
+            graph.vertices().for_each(|v| graph.insert_edge(v, v, 0));
+            let mut matrix = MatrixGraph::from(&graph);
+            // NOTE: repeated squaring reaches walks of `nvert - 1` edges, i.e. every shortest path
+            let mut walks = 1;
+            while walks < nvert {
+                matrix = matrix.min_plus_product(&matrix);
+                walks *= 2;
+            }
+            graph.vertices().for_each(|u| {
+                let paths = graph.dijkstras(u).unwrap();
+                graph.vertices().for_each(|v| assert_eq!(matrix.weight(u, v), paths.distance_to(v)));
+            });
+        }
+    }
+}
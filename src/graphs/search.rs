@@ -0,0 +1,592 @@
+use std::collections::VecDeque;
+
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::unweighted::Graph;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+// NOTE: the edge classes of ADM 5.8. Tree edges discover a new vertex, back edges lead to an
+// ancestor that is still being searched, forward edges to an already finished descendant and cross
+// edges to an already finished vertex in another branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    Tree,
+    Back,
+    Forward,
+    Cross,
+}
+
+// NOTE: the hooks of a search. Every event can return `Control::Stop` to end the search early, after
+// which the searcher ignores further calls to `search_from` (see `is_done`)
+pub trait GraphVisitor {
+    fn discover(&mut self, _v: usize) -> Control { Control::Continue }
+    fn tree_edge(&mut self, _from: usize, _to: usize) -> Control { Control::Continue }
+    fn back_edge(&mut self, _from: usize, _to: usize) -> Control { Control::Continue }
+    fn forward_edge(&mut self, _from: usize, _to: usize) -> Control { Control::Continue }
+    fn cross_edge(&mut self, _from: usize, _to: usize) -> Control { Control::Continue }
+    fn finish(&mut self, _v: usize) -> Control { Control::Continue }
+}
+
+// NOTE: a search without hooks, for when only the searcher state is needed
+impl GraphVisitor for () {}
+
+// NOTE: closure adapters for the common single-hook cases
+pub struct OnDiscover<F>(pub F);
+pub struct OnFinish<F>(pub F);
+pub struct OnEdge<F>(pub F);
+
+impl<F: FnMut(usize) -> Control> GraphVisitor for OnDiscover<F> {
+    fn discover(&mut self, v: usize) -> Control { (self.0)(v) }
+}
+
+impl<F: FnMut(usize) -> Control> GraphVisitor for OnFinish<F> {
+    fn finish(&mut self, v: usize) -> Control { (self.0)(v) }
+}
+
+impl<F: FnMut(usize, usize, EdgeKind) -> Control> GraphVisitor for OnEdge<F> {
+    fn tree_edge(&mut self, from: usize, to: usize) -> Control { (self.0)(from, to, EdgeKind::Tree) }
+    fn back_edge(&mut self, from: usize, to: usize) -> Control { (self.0)(from, to, EdgeKind::Back) }
+    fn forward_edge(&mut self, from: usize, to: usize) -> Control { (self.0)(from, to, EdgeKind::Forward) }
+    fn cross_edge(&mut self, from: usize, to: usize) -> Control { (self.0)(from, to, EdgeKind::Cross) }
+}
+
+// NOTE: one step of a search, in the order the visitor hooks would see it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchEvent {
+    Discover(usize),
+    Edge(usize, usize, EdgeKind),
+    Finish(usize),
+}
+
+pub struct BreadthFirstSearcher<'a, G: GraphLike = Graph> {
+    graph: &'a G,
+    parents: Vec<Option<usize>>,
+    discovered: Vec<bool>,
+    processed: Vec<bool>,
+    done: bool,
+}
+
+pub struct DepthFirstSearcher<'a, G: GraphLike = Graph> {
+    graph: &'a G,
+    parents: Vec<Option<usize>>,
+    discovered: Vec<bool>,
+    processed: Vec<bool>,
+
+    entry_time: Vec<Option<usize>>,
+    exit_time: Vec<Option<usize>>,
+    time: usize,
+    done: bool,
+}
+
+// NOTE: the queue of a breadth-first search along with the vertex being processed and the
+// neighbors it has left to look at
+struct BfsWalk<'a, G: GraphLike + 'a> {
+    queue: VecDeque<usize>,
+    current: Option<(usize, G::Neighbors<'a>)>,
+}
+
+// NOTE: the recursion stack of a depth-first search, made explicit so long paths cannot overflow
+// the call stack. Each frame holds a vertex, its remaining neighbors and whether the edge back to
+// its parent was skipped yet, and `pending` is a vertex reached by a tree edge but not entered yet
+struct DfsWalk<'a, G: GraphLike + 'a> {
+    stack: Vec<(usize, G::Neighbors<'a>, bool)>,
+    pending: Option<usize>,
+}
+
+// NOTE: a search as a lazy sequence of events, which can be stopped at any point by simply not
+// asking for more. The searcher state can be inspected in between
+pub struct BfsEvents<'a, G: GraphLike = Graph> {
+    searcher: BreadthFirstSearcher<'a, G>,
+    walk: BfsWalk<'a, G>,
+}
+
+pub struct DfsEvents<'a, G: GraphLike = Graph> {
+    searcher: DepthFirstSearcher<'a, G>,
+    walk: DfsWalk<'a, G>,
+}
+
+impl SearchEvent {
+    pub fn discovered(self) -> Option<usize> {
+        match self {
+            SearchEvent::Discover(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn edge(self) -> Option<(usize, usize, EdgeKind)> {
+        match self {
+            SearchEvent::Edge(from, to, kind) => Some((from, to, kind)),
+            _ => None,
+        }
+    }
+
+    fn dispatch(self, visitor: &mut impl GraphVisitor) -> Control {
+        match self {
+            SearchEvent::Discover(v) => visitor.discover(v),
+            SearchEvent::Edge(from, to, EdgeKind::Tree) => visitor.tree_edge(from, to),
+            SearchEvent::Edge(from, to, EdgeKind::Back) => visitor.back_edge(from, to),
+            SearchEvent::Edge(from, to, EdgeKind::Forward) => visitor.forward_edge(from, to),
+            SearchEvent::Edge(from, to, EdgeKind::Cross) => visitor.cross_edge(from, to),
+            SearchEvent::Finish(v) => visitor.finish(v),
+        }
+    }
+}
+
+impl<'a, G: GraphLike> BreadthFirstSearcher<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let nvert = graph.vertices().len();
+        let mut parents = Vec::with_capacity(nvert);
+        let mut processed = Vec::with_capacity(nvert);
+        let mut discovered = Vec::with_capacity(nvert);
+        (0..nvert).for_each(|_| {
+            processed.push(false);
+            discovered.push(false);
+            parents.push(None);
+        });
+        BreadthFirstSearcher { graph, parents, discovered, processed, done: false }
+    }
+
+    pub fn search_from(&mut self, start: usize, visitor: &mut impl GraphVisitor) {
+        if self.done { return; }
+        let mut walk = BfsWalk::new(self, start);
+        while let Some(event) = walk.next_event(self) {
+            if self.stopped(event.dispatch(visitor)) { return; }
+        }
+    }
+
+    fn stopped(&mut self, control: Control) -> bool {
+        if control == Control::Stop { self.done = true; }
+        self.done
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn is_discovered(&self, v: usize) -> bool {
+        self.discovered[v]
+    }
+
+    pub fn is_processed(&self, v: usize) -> bool {
+        self.processed[v]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl<'a, G: GraphLike> DepthFirstSearcher<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let nvert = graph.vertices().len();
+        let mut parents = Vec::with_capacity(nvert);
+        let mut processed = Vec::with_capacity(nvert);
+        let mut discovered = Vec::with_capacity(nvert);
+        let mut entry_time = Vec::with_capacity(nvert);
+        let mut exit_time = Vec::with_capacity(nvert);
+        (0..nvert).for_each(|_| {
+            processed.push(false);
+            discovered.push(false);
+            parents.push(None);
+            entry_time.push(None);
+            exit_time.push(None);
+        });
+        DepthFirstSearcher { graph, parents, discovered, processed, time: 0, entry_time, exit_time, done: false }
+    }
+
+    pub fn search_from(&mut self, start: usize, visitor: &mut impl GraphVisitor) {
+        if self.done { return; }
+        let mut walk = DfsWalk::new(start);
+        while let Some(event) = walk.next_event(self) {
+            if self.stopped(event.dispatch(visitor)) { return; }
+        }
+    }
+
+    fn stopped(&mut self, control: Control) -> bool {
+        if control == Control::Stop { self.done = true; }
+        self.done
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn is_discovered(&self, v: usize) -> bool {
+        self.discovered[v]
+    }
+
+    pub fn is_processed(&self, v: usize) -> bool {
+        self.processed[v]
+    }
+
+    pub fn entry_time(&self, v: usize) -> Option<usize> {
+        self.entry_time[v]
+    }
+
+    pub fn exit_time(&self, v: usize) -> Option<usize> {
+        self.exit_time[v]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl<'a, G: GraphLike> BfsWalk<'a, G> {
+    fn new(searcher: &mut BreadthFirstSearcher<'a, G>, start: usize) -> Self {
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        searcher.discovered[start] = true;
+        BfsWalk { queue, current: None }
+    }
+
+    // NOTE: `Discover` and `Finish` bracket the processing of a vertex once it leaves the queue.
+    // Breadth-first search has no notion of finished subtrees, so every edge that does not
+    // discover a vertex is reported as a cross edge (once per undirected edge)
+    fn next_event(&mut self, searcher: &mut BreadthFirstSearcher<'a, G>) -> Option<SearchEvent> {
+        let graph = searcher.graph;
+        loop {
+            let Some((v, neighbors)) = self.current.as_mut() else {
+                let v = self.queue.pop_front()?;
+                searcher.processed[v] = true;
+                self.current = Some((v, graph.neighbors(v)));
+                return Some(SearchEvent::Discover(v));
+            };
+            let v = *v;
+            match neighbors.next() {
+                None => {
+                    self.current = None;
+                    return Some(SearchEvent::Finish(v));
+                },
+                Some(u) if !searcher.discovered[u] => {
+                    self.queue.push_back(u);
+                    searcher.discovered[u] = true;
+                    searcher.parents[u] = Some(v);
+                    return Some(SearchEvent::Edge(v, u, EdgeKind::Tree));
+                },
+                Some(u) if !searcher.processed[u] || graph.is_directed() => {
+                    return Some(SearchEvent::Edge(v, u, EdgeKind::Cross));
+                },
+                Some(_) => {},
+            }
+        }
+    }
+}
+
+impl<'a, G: GraphLike> DfsWalk<'a, G> {
+    fn new(start: usize) -> Self {
+        DfsWalk { stack: Vec::new(), pending: Some(start) }
+    }
+
+    // NOTE: undirected graphs only have tree and back edges, each reported once. Seeing the tree
+    // edge again from the child is skipped once, so a parallel edge to the parent is a back edge
+    fn next_event(&mut self, searcher: &mut DepthFirstSearcher<'a, G>) -> Option<SearchEvent> {
+        let graph = searcher.graph;
+        if let Some(v) = self.pending.take() {
+            searcher.discovered[v] = true;
+            searcher.time += 1;
+            searcher.entry_time[v] = Some(searcher.time);
+            self.stack.push((v, graph.neighbors(v), false));
+            return Some(SearchEvent::Discover(v));
+        }
+        loop {
+            let (v, neighbors, skipped_parent) = self.stack.last_mut()?;
+            let v = *v;
+            let Some(u) = neighbors.next() else {
+                self.stack.pop();
+                searcher.time += 1;
+                searcher.exit_time[v] = Some(searcher.time);
+                searcher.processed[v] = true;
+                return Some(SearchEvent::Finish(v));
+            };
+            let kind = if !searcher.discovered[u] {
+                searcher.parents[u] = Some(v);
+                self.pending = Some(u);
+                EdgeKind::Tree
+            } else if !graph.is_directed() {
+                if searcher.parents[v] == Some(u) && !*skipped_parent {
+                    *skipped_parent = true;
+                    continue;
+                }
+                if searcher.processed[u] { continue; }
+                EdgeKind::Back
+            } else if !searcher.processed[u] {
+                EdgeKind::Back
+            } else if searcher.entry_time[v] < searcher.entry_time[u] {
+                EdgeKind::Forward
+            } else {
+                EdgeKind::Cross
+            };
+            return Some(SearchEvent::Edge(v, u, kind));
+        }
+    }
+}
+
+impl<'a, G: GraphLike> BfsEvents<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> Self {
+        let mut searcher = BreadthFirstSearcher::new(graph);
+        let walk = BfsWalk::new(&mut searcher, start);
+        BfsEvents { searcher, walk }
+    }
+
+    pub fn searcher(&self) -> &BreadthFirstSearcher<'a, G> {
+        &self.searcher
+    }
+}
+
+impl<'a, G: GraphLike> Iterator for BfsEvents<'a, G> {
+    type Item = SearchEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walk.next_event(&mut self.searcher)
+    }
+}
+
+impl<'a, G: GraphLike> DfsEvents<'a, G> {
+    pub fn new(graph: &'a G, start: usize) -> Self {
+        DfsEvents { searcher: DepthFirstSearcher::new(graph), walk: DfsWalk::new(start) }
+    }
+
+    pub fn searcher(&self) -> &DepthFirstSearcher<'a, G> {
+        &self.searcher
+    }
+}
+
+impl<'a, G: GraphLike> Iterator for DfsEvents<'a, G> {
+    type Item = SearchEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.walk.next_event(&mut self.searcher)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::weighted::WeightedGraph;
+    use crate::graphs::test_utils::XorShift;
+
+    // NOTE: records every event in order
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<(&'static str, usize, usize)>,
+        stop_at: Option<usize>,
+    }
+
+    impl GraphVisitor for Recorder {
+        fn discover(&mut self, v: usize) -> Control {
+            self.events.push(("discover", v, v));
+            if self.stop_at == Some(v) { Control::Stop } else { Control::Continue }
+        }
+        fn tree_edge(&mut self, from: usize, to: usize) -> Control {
+            self.events.push(("tree", from, to));
+            Control::Continue
+        }
+        fn back_edge(&mut self, from: usize, to: usize) -> Control {
+            self.events.push(("back", from, to));
+            Control::Continue
+        }
+        fn forward_edge(&mut self, from: usize, to: usize) -> Control {
+            self.events.push(("forward", from, to));
+            Control::Continue
+        }
+        fn cross_edge(&mut self, from: usize, to: usize) -> Control {
+            self.events.push(("cross", from, to));
+            Control::Continue
+        }
+        fn finish(&mut self, v: usize) -> Control {
+            self.events.push(("finish", v, v));
+            Control::Continue
+        }
+    }
+
+    fn kinds(events: &[(&'static str, usize, usize)], kind: &str) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = events.iter().filter(|event| event.0 == kind).map(|event| (event.1, event.2)).collect();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn dfs_directed() {
+        // NOTE: 0 -> 1 -> 2 -> 0 (back), 0 -> 2 (forward), 3 -> 1 (cross). Edges are prepended to
+        // the adjacency lists, so 0 -> 1 is inserted last to be searched first
+        let mut graph = Graph::new(4, true);
+        graph.insert_edge(0, 2);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 0);
+        graph.insert_edge(3, 1);
+        let mut dfs = graph.init_dfs();
+        let mut recorder = Recorder::default();
+        graph.vertices().for_each(|v| if !dfs.is_discovered(v) { dfs.search_from(v, &mut recorder) });
+        assert_eq!(kinds(&recorder.events, "tree"), vec![(0, 1), (1, 2)]);
+        assert_eq!(kinds(&recorder.events, "back"), vec![(2, 0)]);
+        assert_eq!(kinds(&recorder.events, "forward"), vec![(0, 2)]);
+        assert_eq!(kinds(&recorder.events, "cross"), vec![(3, 1)]);
+        assert_eq!((dfs.entry_time(0), dfs.exit_time(0)), (Some(1), Some(6)));
+        assert_eq!((dfs.entry_time(2), dfs.exit_time(2)), (Some(3), Some(4)));
+        assert_eq!(dfs.parent(2), Some(1));
+        assert_eq!(recorder.events.first(), Some(&("discover", 0, 0)));
+        assert_eq!(recorder.events.last(), Some(&("finish", 3, 3)));
+    }
+
+    #[test]
+    fn dfs_undirected() {
+        let mut graph = Graph::new(4, false);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 0);
+        graph.insert_edge(2, 3);
+        let mut recorder = Recorder::default();
+        graph.init_dfs().search_from(0, &mut recorder);
+        assert_eq!(kinds(&recorder.events, "tree").len(), 3);
+        assert_eq!(kinds(&recorder.events, "back").len(), 1);
+        assert!(kinds(&recorder.events, "forward").is_empty() && kinds(&recorder.events, "cross").is_empty());
+    }
+
+    #[test]
+    fn bfs() {
+        let mut graph = WeightedGraph::new(5, false);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(0, 2, 1);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(2, 3, 1);
+        let mut bfs = BreadthFirstSearcher::new(&graph);
+        let mut order = Vec::new();
+        bfs.search_from(0, &mut OnDiscover(|v| {
+            order.push(v);
+            Control::Continue
+        }));
+        order[1..3].sort();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+        assert_eq!(bfs.parent(3), Some(2));
+        assert!(!bfs.is_discovered(4));
+
+        let mut edges = Vec::new();
+        BreadthFirstSearcher::new(&graph).search_from(0, &mut OnEdge(|from: usize, to: usize, kind| {
+            edges.push((from.min(to), from.max(to), kind));
+            Control::Continue
+        }));
+        edges.sort_by_key(|&(u, v, _)| (u, v));
+        assert_eq!(edges.iter().filter(|edge| edge.2 == EdgeKind::Tree).count(), 3);
+        assert_eq!(edges.iter().filter(|edge| edge.2 == EdgeKind::Cross).collect::<Vec<_>>(), vec![&(1, 2, EdgeKind::Cross)]);
+    }
+
+    // NOTE: the recursive depth-first search this module used to run, kept as a reference for the
+    // entry and exit times of the explicit-stack version
+    fn recursive_times(graph: &Graph) -> Vec<(usize, usize)> {
+        fn search(graph: &Graph, v: usize, time: &mut usize, times: &mut Vec<(usize, usize)>) {
+            *time += 1;
+            times[v].0 = *time;
+            for u in graph.neighbors(v) {
+                if times[u].0 == 0 { search(graph, u, time, times); }
+            }
+            *time += 1;
+            times[v].1 = *time;
+        }
+        let mut times = vec![(0, 0); graph.vertices().len()];
+        let mut time = 0;
+        graph.vertices().for_each(|v| if times[v].0 == 0 { search(graph, v, &mut time, &mut times) });
+        times
+    }
+
+    #[test]
+    fn dfs_times_random() {
+        let mut rng = XorShift(0xbb67ae8584caa73b);
+        for round in 0..40 {
+            let nvert = 1 + rng.below(30);
+            let mut graph = Graph::new(nvert, round % 2 == 0);
+            (0..rng.below(nvert * 3)).for_each(|_| graph.insert_edge(rng.below(nvert), rng.below(nvert)));
+            let mut dfs = graph.init_dfs();
+            graph.vertices().for_each(|v| if !dfs.is_discovered(v) { dfs.search_from(v, &mut ()) });
+            let times: Vec<(usize, usize)> = graph.vertices()
+                .map(|v| (dfs.entry_time(v).unwrap(), dfs.exit_time(v).unwrap()))
+                .collect();
+            assert_eq!(times, recursive_times(&graph));
+        }
+    }
+
+    #[test]
+    fn long_chain() {
+        let nvert = 1_000_000;
+        let mut graph = Graph::new(nvert, true);
+        (1..nvert).for_each(|v| graph.insert_edge(v - 1, v));
+        let mut dfs = graph.init_dfs();
+        dfs.search_from(0, &mut ());
+        assert_eq!((dfs.entry_time(nvert - 1), dfs.exit_time(0)), (Some(nvert), Some(2 * nvert)));
+        assert_eq!(graph.dfs_iter(0).count(), nvert);
+    }
+
+    #[test]
+    fn iterators() {
+        let mut graph = Graph::new(7, false);
+        graph.insert_edge(0, 2);
+        graph.insert_edge(0, 1);
+        graph.insert_edge(1, 3);
+        graph.insert_edge(1, 4);
+        graph.insert_edge(2, 5);
+        graph.insert_edge(5, 0);
+        // NOTE: adjacency lists are in reverse insertion order, e.g. 0: 5 1 2
+        assert_eq!(graph.bfs_iter(0).collect::<Vec<_>>(), vec![0, 5, 1, 2, 4, 3]);
+        assert_eq!(graph.dfs_iter(0).collect::<Vec<_>>(), vec![0, 5, 2, 1, 4, 3]);
+        assert_eq!(graph.dfs_iter(6).collect::<Vec<_>>(), vec![6]);
+        assert_eq!(graph.bfs_iter(0).take_while(|&v| v != 2).count(), 3);
+
+        let back_edges: Vec<(usize, usize, EdgeKind)> = graph.dfs_events(0)
+            .filter_map(SearchEvent::edge)
+            .filter(|&(_, _, kind)| kind != EdgeKind::Tree)
+            .collect();
+        assert_eq!(back_edges, vec![(2, 0, EdgeKind::Back)]);
+
+        // NOTE: the searcher can be inspected while the iterator is half way through
+        let mut events = graph.dfs_events(0);
+        assert!(events.by_ref().any(|event| event == SearchEvent::Finish(4)));
+        assert!(events.searcher().is_processed(4) && !events.searcher().is_processed(1));
+        assert_eq!(events.searcher().parent(3), None);
+
+        // NOTE: the iterators are generic over the graph type
+        let mut weighted = WeightedGraph::new(3, true);
+        weighted.insert_edge(0, 1, 5);
+        weighted.insert_edge(1, 2, 5);
+        weighted.insert_edge(2, 0, 5);
+        let events: Vec<SearchEvent> = DfsEvents::new(&weighted, 1).collect();
+        assert_eq!(events, vec![
+            SearchEvent::Discover(1),
+            SearchEvent::Edge(1, 2, EdgeKind::Tree),
+            SearchEvent::Discover(2),
+            SearchEvent::Edge(2, 0, EdgeKind::Tree),
+            SearchEvent::Discover(0),
+            SearchEvent::Edge(0, 1, EdgeKind::Back),
+            SearchEvent::Finish(0),
+            SearchEvent::Finish(2),
+            SearchEvent::Finish(1),
+        ]);
+        assert_eq!(BfsEvents::new(&weighted, 0).filter_map(SearchEvent::discovered).collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn early_termination() {
+        let mut graph = Graph::new(6, true);
+        (0..5).for_each(|v| graph.insert_edge(v, v + 1));
+        let mut dfs = graph.init_dfs();
+        let mut recorder = Recorder { stop_at: Some(3), ..Recorder::default() };
+        dfs.search_from(0, &mut recorder);
+        assert!(dfs.is_done());
+        assert!(dfs.is_discovered(3) && !dfs.is_discovered(4));
+        assert!(recorder.events.iter().all(|event| event.0 != "finish"));
+        dfs.search_from(5, &mut recorder);
+        assert!(!dfs.is_discovered(5));
+
+        let mut bfs = graph.init_bfs();
+        let mut finished = 0;
+        bfs.search_from(0, &mut OnFinish(|_| {
+            finished += 1;
+            if finished == 2 { Control::Stop } else { Control::Continue }
+        }));
+        assert!(bfs.is_done() && bfs.is_processed(1) && !bfs.is_processed(2));
+    }
+}
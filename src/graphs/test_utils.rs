@@ -0,0 +1,27 @@
+use super::weighted::WeightedGraph;
+
+// NOTE: a tiny xorshift generator so the randomized graph tests stay deterministic without
+// pulling in a dependency
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    pub fn weighted_graph(&mut self, nvert: usize, nedges: usize, directed: bool, weights: std::ops::Range<i32>) -> WeightedGraph {
+        let mut graph = WeightedGraph::new(nvert, directed);
+        (0..nedges).for_each(|_| {
+            let weight = weights.start + self.below((weights.end - weights.start) as usize) as i32;
+            graph.insert_edge(self.below(nvert), self.below(nvert), weight);
+        });
+        graph
+    }
+}
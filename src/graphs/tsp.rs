@@ -0,0 +1,389 @@
+use std::fmt;
+
+use crate::graphs::weight::{self, Weight};
+use crate::graphs::weighted::WeightedGraph;
+
+// NOTE: Held-Karp keeps a table of 2^(n-1) * (n-1) weights, which is already ~10M entries at 20
+const HELD_KARP_MAX_VERTICES: usize = 20;
+
+#[derive(Debug, PartialEq)]
+pub enum TspError {
+    Directed,
+    MissingEdge(usize, usize),
+    TooLarge(usize),
+    InvalidStart(usize),
+    InvalidTour,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tour<W: Weight = i32> {
+    // NOTE: every vertex exactly once in visiting order; the edge back to the first is implied
+    order: Vec<usize>,
+    cost: W,
+}
+
+impl<W: Weight> WeightedGraph<W> {
+    // NOTE: the exact optimum by dynamic programming over subsets, O(2^n n^2). `best[mask][j]` is
+    // the cheapest path that leaves 0, visits exactly the vertices in `mask` and ends at `j`
+    // (vertex `v` is bit `v - 1`, since 0 is always the start)
+    pub fn held_karp(&self) -> Result<Tour<W>, TspError> {
+        let costs = self.tour_costs()?;
+        let nvert = costs.len();
+        if nvert > HELD_KARP_MAX_VERTICES { return Err(TspError::TooLarge(nvert)); }
+        if nvert <= 1 { return Ok(Tour::new(&costs, (0..nvert).collect())); }
+
+        let nsubsets = 1 << (nvert - 1);
+        let mut best: Vec<Vec<W>> = vec![vec![W::INFINITY; nvert]; nsubsets];
+        (1..nvert).for_each(|j| best[1 << (j - 1)][j] = costs[0][j]);
+        for mask in 1..nsubsets {
+            for j in (1..nvert).filter(|j| mask & (1 << (j - 1)) != 0) {
+                if best[mask][j] == W::INFINITY { continue; }
+                for next in (1..nvert).filter(|next| mask & (1 << (next - 1)) == 0) {
+                    let candidate = weight::extend(best[mask][j], costs[j][next]);
+                    let extended = mask | (1 << (next - 1));
+                    if candidate < best[extended][next] { best[extended][next] = candidate; }
+                }
+            }
+        }
+
+        // NOTE: walk the table backwards, at each step finding a predecessor whose entry explains
+        // the current one, instead of storing a parent table as large as `best`
+        let full = nsubsets - 1;
+        let mut cur_vertex = (1..nvert)
+            .min_by(|&a, &b| weight::extend(best[full][a], costs[a][0]).total_cmp(&weight::extend(best[full][b], costs[b][0])))
+            .expect("there are at least two vertices");
+        let mut mask = full;
+        let mut order = vec![cur_vertex];
+        while mask != 1 << (cur_vertex - 1) {
+            let rest = mask & !(1 << (cur_vertex - 1));
+            let prev = (1..nvert)
+                .filter(|prev| rest & (1 << (prev - 1)) != 0)
+                .find(|&prev| weight::extend(best[rest][prev], costs[prev][cur_vertex]) == best[mask][cur_vertex])
+                .expect("every table entry is explained by a smaller subset");
+            order.push(prev);
+            mask = rest;
+            cur_vertex = prev;
+        }
+        order.push(0);
+        order.reverse();
+        Ok(Tour::new(&costs, order))
+    }
+
+    // NOTE: always move to the closest unvisited vertex, O(n^2)
+    pub fn nearest_neighbor_tour(&self, start: usize) -> Result<Tour<W>, TspError> {
+        let costs = self.tour_costs()?;
+        let nvert = costs.len();
+        if start >= nvert { return Err(TspError::InvalidStart(start)); }
+        let mut visited = vec![false; nvert];
+        let mut order = Vec::with_capacity(nvert);
+        let mut cur_vertex = Some(start);
+        while let Some(v) = cur_vertex {
+            visited[v] = true;
+            order.push(v);
+            cur_vertex = (0..nvert).filter(|&u| !visited[u]).min_by(|&a, &b| costs[v][a].total_cmp(&costs[v][b]));
+        }
+        Ok(Tour::new(&costs, order))
+    }
+
+    // NOTE: walking the MST in preorder shortcuts the closed walk that uses every tree edge twice,
+    // so with the triangle inequality the tour costs at most twice the MST, hence twice the optimum
+    pub fn mst_doubling_tour(&self) -> Result<Tour<W>, TspError> {
+        let costs = self.tour_costs()?;
+        let nvert = costs.len();
+        if nvert == 0 { return Ok(Tour::new(&costs, Vec::new())); }
+        let mst = self.prims(0).map_err(|_| TspError::Directed)?;
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); nvert];
+        (0..nvert).for_each(|v| {
+            if let Some(p) = mst.parent(v) { children[p].push(v); }
+        });
+
+        let mut order = Vec::with_capacity(nvert);
+        let mut stack = vec![0];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            stack.extend(children[v].iter().rev());
+        }
+        Ok(Tour::new(&costs, order))
+    }
+
+    // NOTE: replace edges `(a, b)` and `(c, d)` by `(a, c)` and `(b, d)` (reversing the stretch
+    // between them) while that makes the tour cheaper. A sum that overflows is never an improvement
+    pub fn two_opt(&self, tour: &Tour<W>) -> Result<Tour<W>, TspError> {
+        let costs = self.tour_costs()?;
+        check_tour(&costs, tour)?;
+        let mut order = tour.order.clone();
+        let nvert = order.len();
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..nvert.saturating_sub(2) {
+                for j in i + 2..nvert {
+                    if i == 0 && j == nvert - 1 { continue; }
+                    let (a, b, c, d) = (order[i], order[i + 1], order[j], order[(j + 1) % nvert]);
+                    if weight::extend(costs[a][c], costs[b][d]) < weight::extend(costs[a][b], costs[c][d]) {
+                        order[i + 1..=j].reverse();
+                        improved = true;
+                    }
+                }
+            }
+        }
+        Ok(Tour::new(&costs, order))
+    }
+
+    // NOTE: move a run of up to three consecutive vertices (possibly reversed) to another place in
+    // the tour while that makes it cheaper. The first vertex never moves, so the tour keeps its start
+    pub fn or_opt(&self, tour: &Tour<W>) -> Result<Tour<W>, TspError> {
+        let costs = self.tour_costs()?;
+        check_tour(&costs, tour)?;
+        let mut order = tour.order.clone();
+        let nvert = order.len();
+        let mut improved = true;
+        while improved {
+            improved = false;
+            // NOTE: a run needs at least three other vertices around it to have somewhere to go
+            'search: for len in (1..=3).filter(|len| len + 3 <= nvert) {
+                for i in 1..=nvert - len {
+                    let (prev, next) = (order[i - 1], order[(i + len) % nvert]);
+                    let (first, last) = (order[i], order[i + len - 1]);
+                    let removed = weight::extend(costs[prev][first], costs[last][next]);
+                    let mut rest: Vec<usize> = order[..i].to_vec();
+                    rest.extend_from_slice(&order[i + len..]);
+                    for j in (0..rest.len()).filter(|&j| j != i - 1) {
+                        let (a, b) = (rest[j], rest[(j + 1) % rest.len()]);
+                        let forward = weight::extend(costs[a][first], costs[last][b]);
+                        let backward = weight::extend(costs[a][last], costs[first][b]);
+                        let reversed = backward < forward;
+                        let inserted = if reversed { backward } else { forward };
+                        if weight::extend(costs[prev][next], inserted) < weight::extend(removed, costs[a][b]) {
+                            let mut segment = order[i..i + len].to_vec();
+                            if reversed { segment.reverse(); }
+                            rest.splice(j + 1..j + 1, segment);
+                            order = rest;
+                            improved = true;
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(Tour::new(&costs, order))
+    }
+
+    // NOTE: the cost matrix of a complete undirected graph, using the lightest of parallel edges
+    fn tour_costs(&self) -> Result<Vec<Vec<W>>, TspError> {
+        if self.is_directed() { return Err(TspError::Directed); }
+        let nvert = self.vcapacity();
+        let mut costs = vec![vec![W::INFINITY; nvert]; nvert];
+        (0..nvert).for_each(|u| {
+            costs[u][u] = W::ZERO;
+            self.incident(u).filter(|&(v, _)| v != u).for_each(|(v, weight)| {
+                if weight < costs[u][v] { costs[u][v] = weight; }
+            });
+        });
+        match (0..nvert).flat_map(|u| (0..nvert).map(move |v| (u, v))).find(|&(u, v)| costs[u][v] == W::INFINITY) {
+            Some((u, v)) => Err(TspError::MissingEdge(u, v)),
+            None => Ok(costs),
+        }
+    }
+}
+
+// NOTE: the local improvements only accept tours visiting every vertex of this graph exactly once,
+// such as the ones built for it by the other methods
+fn check_tour<W: Weight>(costs: &[Vec<W>], tour: &Tour<W>) -> Result<(), TspError> {
+    let mut seen = vec![false; costs.len()];
+    if tour.order.len() != costs.len() { return Err(TspError::InvalidTour); }
+    for &v in &tour.order {
+        if v >= costs.len() || seen[v] { return Err(TspError::InvalidTour); }
+        seen[v] = true;
+    }
+    Ok(())
+}
+
+impl<W: Weight> Tour<W> {
+    fn new(costs: &[Vec<W>], order: Vec<usize>) -> Self {
+        let closing = order.first().zip(order.last()).map(|(&first, &last)| (last, first));
+        let cost = order.windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .chain(closing.filter(|_| order.len() > 1))
+            .fold(W::ZERO, |total, (u, v)| total.saturating_add(costs[u][v]));
+        Tour { order, cost }
+    }
+
+    pub fn vertices(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn cost(&self) -> W {
+        self.cost
+    }
+}
+
+impl<W: Weight> fmt::Display for Tour<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.order.iter().try_for_each(|v| write!(f, "{} -> ", v))?;
+        if let Some(first) = self.order.first() { write!(f, "{}", first)?; }
+        write!(f, " [w:{}]", self.cost)
+    }
+}
+
+impl fmt::Display for TspError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TspError::Directed => write!(f, "tours are only defined on undirected graphs"),
+            TspError::MissingEdge(u, v) => write!(f, "graph is not complete, edge {} - {} is missing", u, v),
+            TspError::TooLarge(nvert) => write!(f, "{} vertices is too many for Held-Karp (at most {})", nvert, HELD_KARP_MAX_VERTICES),
+            TspError::InvalidStart(v) => write!(f, "start vertex {} is not in the graph", v),
+            TspError::InvalidTour => write!(f, "tour does not visit every vertex of the graph exactly once"),
+        }
+    }
+}
+
+impl std::error::Error for TspError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::test_utils::XorShift;
+
+    // NOTE: complete graph on random grid points with Manhattan distances, which is a metric
+    fn manhattan(rng: &mut XorShift, nvert: usize) -> WeightedGraph {
+        let points: Vec<(i32, i32)> = (0..nvert).map(|_| (rng.below(100) as i32, rng.below(100) as i32)).collect();
+        let mut graph = WeightedGraph::new(nvert, false);
+        (0..nvert).for_each(|u| (u + 1..nvert).for_each(|v| {
+            graph.insert_edge(u, v, (points[u].0 - points[v].0).abs() + (points[u].1 - points[v].1).abs());
+        }));
+        graph
+    }
+
+    fn brute_force(costs: &[Vec<i32>]) -> i32 {
+        fn permute(costs: &[Vec<i32>], order: &mut Vec<usize>, cost: i32, best: &mut i32) {
+            let last = *order.last().unwrap();
+            if order.len() == costs.len() {
+                *best = (*best).min(cost + costs[last][0]);
+                return;
+            }
+            for v in 0..costs.len() {
+                if order.contains(&v) { continue; }
+                order.push(v);
+                permute(costs, order, cost + costs[last][v], best);
+                order.pop();
+            }
+        }
+        let mut best = i32::MAX;
+        permute(costs, &mut vec![0], 0, &mut best);
+        best
+    }
+
+    fn check(graph: &WeightedGraph, tour: &Tour) {
+        let costs = graph.tour_costs().unwrap();
+        let mut vertices = tour.vertices().to_vec();
+        vertices.sort();
+        assert_eq!(vertices, (0..costs.len()).collect::<Vec<_>>());
+        let n = vertices.len();
+        let cost: i32 = (0..n).map(|i| costs[tour.vertices()[i]][tour.vertices()[(i + 1) % n]]).sum();
+        assert_eq!(tour.cost(), cost);
+    }
+
+    #[test]
+    fn held_karp() {
+        let mut graph = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 10);
+        graph.insert_edge(0, 2, 15);
+        graph.insert_edge(0, 3, 20);
+        graph.insert_edge(1, 2, 35);
+        graph.insert_edge(1, 3, 25);
+        graph.insert_edge(2, 3, 30);
+        let tour = graph.held_karp().unwrap();
+        check(&graph, &tour);
+        assert_eq!(tour.cost(), 80);
+        assert_eq!(tour.to_string(), format!("{} -> 0 [w:80]", tour.vertices().iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" -> ")));
+
+        let mut rng = XorShift(0xabcdef0123456789);
+        for _ in 0..20 {
+            let nvert = 1 + rng.below(8);
+            let graph = manhattan(&mut rng, nvert);
+            let tour = graph.held_karp().unwrap();
+            check(&graph, &tour);
+            assert_eq!(tour.cost(), brute_force(&graph.tour_costs().unwrap()));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut graph = WeightedGraph::new(3, false);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(1, 2, 1);
+        assert_eq!(graph.held_karp().unwrap_err(), TspError::MissingEdge(0, 2));
+        let graph: WeightedGraph = WeightedGraph::new(2, true);
+        assert_eq!(graph.nearest_neighbor_tour(0).unwrap_err(), TspError::Directed);
+        let graph = manhattan(&mut XorShift(7), HELD_KARP_MAX_VERTICES + 1);
+        assert_eq!(graph.held_karp().unwrap_err(), TspError::TooLarge(HELD_KARP_MAX_VERTICES + 1));
+
+        let graph = manhattan(&mut XorShift(11), 5);
+        assert_eq!(graph.nearest_neighbor_tour(5).unwrap_err(), TspError::InvalidStart(5));
+        let tour = graph.nearest_neighbor_tour(3).unwrap();
+        assert_eq!(tour.vertices()[0], 3);
+        assert_eq!(graph.or_opt(&tour).unwrap().vertices()[0], 3);
+        let smaller = manhattan(&mut XorShift(11), 4);
+        let foreign = smaller.nearest_neighbor_tour(0).unwrap();
+        assert_eq!(graph.two_opt(&foreign).unwrap_err(), TspError::InvalidTour);
+        assert_eq!(graph.or_opt(&foreign).unwrap_err(), TspError::InvalidTour);
+        let foreign = Tour { order: vec![0, 1, 2, 3, 3], cost: 0 };
+        assert_eq!(graph.two_opt(&foreign).unwrap_err(), TspError::InvalidTour);
+        let foreign = Tour { order: vec![0, 1, 2, 3, 7], cost: 0 };
+        assert_eq!(smaller.or_opt(&foreign).unwrap_err(), TspError::InvalidTour);
+        assert_eq!(graph.or_opt(&foreign).unwrap_err(), TspError::InvalidTour);
+
+        let graph: WeightedGraph = WeightedGraph::new(0, false);
+        assert_eq!(graph.nearest_neighbor_tour(0).unwrap_err(), TspError::InvalidStart(0));
+        assert_eq!(graph.held_karp().unwrap().cost(), 0);
+        assert!(graph.mst_doubling_tour().unwrap().vertices().is_empty());
+    }
+
+    #[test]
+    fn large_weights() {
+        let mut graph = WeightedGraph::new(4, false);
+        (0..4).for_each(|u| (u + 1..4).for_each(|v| graph.insert_edge(u, v, i32::MAX - 10)));
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(2, 3, 1);
+        let tour = graph.nearest_neighbor_tour(0).unwrap();
+        assert_eq!(tour.vertices(), &[0, 1, 2, 3]);
+        assert_eq!(graph.two_opt(&tour).unwrap().vertices(), tour.vertices());
+        assert_eq!(graph.or_opt(&tour).unwrap().vertices(), tour.vertices());
+    }
+
+    #[test]
+    fn heuristics() {
+        let mut rng = XorShift(0x0123456789abcdef);
+        // NOTE: summed over all instances, how much each heuristic overshoots the optimum
+        let (mut optimum, mut nearest, mut doubling, mut improved) = (0, 0, 0, 0);
+        for _ in 0..30 {
+            let nvert = 2 + rng.below(10);
+            let graph = manhattan(&mut rng, nvert);
+            let exact = graph.held_karp().unwrap();
+
+            let nearest_neighbor = graph.nearest_neighbor_tour(0).unwrap();
+            let mst_doubling = graph.mst_doubling_tour().unwrap();
+            let two_opt = graph.two_opt(&nearest_neighbor).unwrap();
+            let or_opt = graph.or_opt(&two_opt).unwrap();
+            [&nearest_neighbor, &mst_doubling, &two_opt, &or_opt].iter().for_each(|tour| {
+                check(&graph, tour);
+                assert!(tour.cost() >= exact.cost());
+            });
+            assert!(mst_doubling.cost() <= 2 * exact.cost());
+            assert!(two_opt.cost() <= nearest_neighbor.cost());
+            assert!(or_opt.cost() <= two_opt.cost());
+            assert_eq!(or_opt.vertices()[0], 0);
+
+            optimum += exact.cost();
+            nearest += nearest_neighbor.cost();
+            doubling += mst_doubling.cost();
+            improved += or_opt.cost();
+        }
+        // NOTE: on these instances nearest neighbor and MST doubling land within 25% and 2-opt
+        // followed by Or-opt within 5% of the optimum
+        assert!(nearest * 100 <= optimum * 125, "nearest neighbor {} vs {}", nearest, optimum);
+        assert!(doubling * 100 <= optimum * 125, "mst doubling {} vs {}", doubling, optimum);
+        assert!(improved * 100 <= optimum * 105, "2-opt + Or-opt {} vs {}", improved, optimum);
+    }
+}
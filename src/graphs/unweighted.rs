@@ -1,10 +1,14 @@
 use std::mem;
-use std::collections;
 use std::fmt;
 
+use crate::graphs::graph_like::GraphLike;
+
 pub struct Graph {
     edges: Vec<Option<Box<Edge>>>,
-    degrees: Vec<i32>,
+    // NOTE: `degrees` counts adjacency entries (out-degree when directed); `in_degrees` is only
+    // kept up to date for directed graphs
+    degrees: Vec<usize>,
+    in_degrees: Vec<usize>,
     nedges: usize,   
     nvert: usize,   
     directed: bool,   
@@ -15,47 +19,32 @@ struct Edge {
     next: Option<Box<Edge>>, 
 }
 
-pub struct BreadthFirstSearcher<'a> {
-    graph: &'a Graph,
-    parents: Vec<Option<usize>>,
-    discovered: Vec<bool>,
-    processed: Vec<bool>
-}
-
-pub struct DepthFirstSearcher<'a> {
-    graph: &'a Graph,
-    parents: Vec<Option<usize>>,
-    discovered: Vec<bool>,
-    processed: Vec<bool>,
-
-    entry_time: Vec<Option<usize>>,
-    exit_time: Vec<Option<usize>>,
-    time: usize,
-    done: bool,
-}
-
 impl Graph {
     pub fn new(vcapacity: usize, directed: bool) -> Self {
         let mut edges = Vec::with_capacity(vcapacity);
         let mut degrees = Vec::with_capacity(vcapacity);
+        let mut in_degrees = Vec::with_capacity(vcapacity);
         (0..vcapacity).for_each(|_| {
             degrees.push(0);
+            in_degrees.push(0);
             edges.push(None);
         });
         Graph {
             edges,
             degrees,
+            in_degrees,
             nedges: 0,
-            nvert: 0,
+            nvert: vcapacity,
             directed,
         }
     }
  
+    // NOTE: an undirected self-loop adds 2 to the degree of its vertex
     pub fn insert_edge(&mut self, i: usize, j: usize) {
         assert!(j < self.edges.len() && i < self.edges.len(), "vertices `i` and `j` must be within capacity");
         self.nedges += 1;
-        self.nvert += 1;
         self.degrees[i] += 1;
+        if self.directed { self.in_degrees[j] += 1 } else { self.degrees[j] += 1 }
         match mem::replace(&mut self.edges[i], None) {
             None => self.edges[i] = Some(Box::new(Edge::new(j, None))),
             edge => self.edges[i] = Some(Box::new(Edge::new(j, edge))),
@@ -69,99 +58,101 @@ impl Graph {
         }
     }
 
-    pub fn init_bfs(&self) -> BreadthFirstSearcher {
-        BreadthFirstSearcher::new(self)
+    // NOTE: removes a single `i -> j` edge (both of its entries when undirected), the most
+    // recently inserted one if there are parallel edges. Returns whether there was one
+    pub fn remove_edge(&mut self, i: usize, j: usize) -> bool {
+        assert!(j < self.edges.len() && i < self.edges.len(), "vertices `i` and `j` must be within capacity");
+        if !unlink(&mut self.edges[i], j) { return false; }
+        if !self.directed { unlink(&mut self.edges[j], i); }
+        self.nedges -= 1;
+        self.degrees[i] -= 1;
+        if self.directed { self.in_degrees[j] -= 1 } else { self.degrees[j] -= 1 }
+        true
     }
 
-    pub fn init_dfs(&self) -> DepthFirstSearcher {
-        DepthFirstSearcher::new(self)
+    // NOTE: drops `v` with all of its edges and compacts the ids, every vertex above `v` moving
+    // down by one so the vertices stay `0..vertex_count()`
+    pub fn remove_vertex(&mut self, v: usize) {
+        assert!(v < self.edges.len(), "vertex `v` must be within capacity");
+        let outgoing: Vec<usize> = self.incident(v).collect();
+        outgoing.into_iter().for_each(|u| { self.remove_edge(v, u); });
+        if self.directed {
+            let incoming: Vec<usize> = self.vertices().filter(|&u| self.incident(u).any(|w| w == v)).collect();
+            incoming.into_iter().for_each(|u| while self.remove_edge(u, v) {});
+        }
+        self.edges.remove(v);
+        self.degrees.remove(v);
+        self.in_degrees.remove(v);
+        self.nvert -= 1;
+        self.edges.iter_mut().for_each(|list| {
+            let mut cur_edge = list.as_deref_mut();
+            while let Some(edge) = cur_edge {
+                if edge.points_to > v { edge.points_to -= 1; }
+                cur_edge = edge.next.as_deref_mut();
+            }
+        });
     }
 
-    pub fn connected_components(&self) -> usize {
-        let mut bfs = self.init_bfs();
-        let mut cc = 0;
-        for i in 0..bfs.discovered.len() {
-            if !bfs.discovered[i] {
-                cc += 1;
-                bfs.search_from(i, None, None, None);
-            }
-        }
-        cc
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.incident(i).any(|v| v == j)
     }
 
-    pub fn find_path(&self, start: usize, end: usize) -> Option<Vec<usize>> {
-        let mut builder = Vec::new();
-        let mut bfs = self.init_bfs();
-        bfs.search_from(start, None, None, None);
-        let mut cur_vertex = end;
-        builder.insert(0, cur_vertex);
-        while let Some(parent) = bfs.parents[cur_vertex] {
-            builder.insert(0, parent);
-            if parent == start { return Some(builder) }
-            cur_vertex = parent;
-        }
-        None 
+    // NOTE: in- plus out-degree when directed
+    pub fn degree(&self, v: usize) -> usize {
+        if self.directed { self.degrees[v] + self.in_degrees[v] } else { self.degrees[v] }
     }
 
-    pub fn find_cycle<'a>(&'a self, start: usize) {
-        let mut dfs = self.init_dfs();
-        fn process_edge<'a>(searcher: &'a mut DepthFirstSearcher<'_>, origin: usize, points_to: usize) {
-            if searcher.parents[points_to].is_none() || origin != searcher.parents[points_to].unwrap() {
-                print!("cycle found: {} {}", points_to, origin);
-                let mut cur_vertex = origin;
-                while let Some(parent) = searcher.parents[cur_vertex] {
-                    print!(" {}", parent);
-                    if parent == points_to { break; }
-                    cur_vertex = parent;
-                }
-                println!("");
-                searcher.done = true;
-            }
-        }
-        dfs.search_from(start, None, Some(process_edge), None);
+    pub fn in_degree(&self, v: usize) -> usize {
+        if self.directed { self.in_degrees[v] } else { self.degrees[v] }
     }
-}
 
-impl<'a> BreadthFirstSearcher<'a> {
-    fn new(graph: &'a Graph) -> Self {
-        let mut parents = Vec::with_capacity(graph.edges.len());
-        let mut processed = Vec::with_capacity(graph.edges.len());
-        let mut discovered = Vec::with_capacity(graph.edges.len());
-        (0..graph.edges.len()).for_each(|_| {
-            processed.push(false); 
-            discovered.push(false); 
-            parents.push(None); 
-        });
-        BreadthFirstSearcher { graph, parents, discovered, processed }
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.degrees[v]
     }
 
-    fn search_from(
-        &mut self, 
-        start: usize,
-        preprocess: Option<fn(&mut Self, usize)>, 
-        process_edge: Option<fn(&mut Self, usize, usize)>, 
-        postprocess: Option<fn(&mut Self, usize)>)
-    { 
-        let mut queue: collections::VecDeque<usize> = collections::VecDeque::new();
-        queue.push_back(start);
-        self.discovered[start] = true;
-        while let Some(v) = queue.pop_front() {
-            preprocess.map(|f| f(self, v));
-            self.processed[v] = true;
-            let mut cur_edge = &self.graph.edges[v];
-            while let Some(incident) = cur_edge {
-                if !self.processed[incident.points_to] || self.graph.directed {
-                    process_edge.map(|f| f(self, v, incident.points_to));
-                }
-                if !self.discovered[incident.points_to] {
-                    queue.push_back(incident.points_to); 
-                    self.discovered[incident.points_to] = true;
-                    self.parents[incident.points_to] = Some(v);
-                }
-                cur_edge = &incident.next;
-            }
-            postprocess.map(|f| f(self, v));
-        }
+    pub fn vertex_count(&self) -> usize {
+        self.nvert
+    }
+
+    // NOTE: an undirected edge counts once even though it is stored at both endpoints
+    pub fn edge_count(&self) -> usize {
+        self.nedges
+    }
+
+    pub(crate) fn vcapacity(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub(crate) fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub(crate) fn incident(&self, v: usize) -> Incident<'_> {
+        Incident { cur_edge: &self.edges[v] }
+    }
+
+    // NOTE: the same vertices with every edge reversed
+    pub fn transpose(&self) -> Graph {
+        let mut transposed = Graph::new(self.edges.len(), self.directed);
+        (0..self.edges.len()).for_each(|u| self.incident(u).for_each(|v| {
+            // NOTE: undirected edges are stored in both lists, only copy them once
+            if self.directed || u <= v { transposed.insert_edge(v, u); }
+        }));
+        transposed
+    }
+}
+
+pub struct Incident<'a> {
+    cur_edge: &'a Option<Box<Edge>>,
+}
+
+impl<'a> Iterator for Incident<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge = self.cur_edge.as_deref()?;
+        self.cur_edge = &edge.next;
+        Some(edge.points_to)
     }
 }
 
@@ -174,52 +165,17 @@ impl Edge {
     }
 }
 
-impl<'a> DepthFirstSearcher<'a> {
-    fn new(graph: &'a Graph) -> Self {
-        let mut parents = Vec::with_capacity(graph.edges.len());
-        let mut processed = Vec::with_capacity(graph.edges.len());
-        let mut discovered = Vec::with_capacity(graph.edges.len());
-        let mut entry_time = Vec::with_capacity(graph.edges.len());
-        let mut exit_time = Vec::with_capacity(graph.edges.len());
-        (0..graph.edges.len()).for_each(|_| {
-            processed.push(false); 
-            discovered.push(false); 
-            parents.push(None); 
-            entry_time.push(None); 
-            exit_time.push(None); 
-        });
-        DepthFirstSearcher { graph, parents, discovered, processed, time: 0, entry_time, exit_time, done: false }
+// NOTE: unlinks the first entry of `list` pointing to `points_to`
+fn unlink(mut list: &mut Option<Box<Edge>>, points_to: usize) -> bool {
+    while list.as_deref().is_some_and(|edge| edge.points_to != points_to) {
+        list = &mut list.as_mut().expect("checked by the loop condition").next;
     }
-
-    pub fn search_from(
-        &mut self, 
-        start: usize, 
-        preprocess: Option<fn(usize)>, 
-        process_edge: Option<fn(&mut Self, usize, usize)>, 
-        postprocess: Option<fn(usize)>) 
-    { 
-        if self.done { return; }
-        self.discovered[start] = true;
-        preprocess.map(|f| f(start));
-        self.time += 1;
-        self.entry_time[start] = Some(self.time);
-        let mut cur_edge = &self.graph.edges[start];
-        while let Some(v) = cur_edge {
-            let not_loop = self.parents[v.points_to].is_some() && self.parents[v.points_to].unwrap() != start;
-            if !self.discovered[v.points_to] {
-                self.parents[v.points_to] = Some(start);
-                process_edge.map(|f| f(self, start, v.points_to));
-                self.search_from(v.points_to, preprocess, process_edge, postprocess);
-            } else if (!self.processed[v.points_to] && not_loop) || self.graph.directed {
-                process_edge.map(|f| f(self, start, v.points_to));
-            }         
-            if self.done { return; }
-            cur_edge = &v.next;
-        }
-        postprocess.map(|f| f(start));
-        self.time += 1;
-        self.exit_time[start] = Some(self.time);
-        self.processed[start] = true;
+    match list.take() {
+        None => false,
+        Some(edge) => {
+            *list = edge.next;
+            true
+        },
     }
 }
 
@@ -249,6 +205,7 @@ impl fmt::Display for Graph {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::graphs::test_utils::XorShift;
 
     #[test]
     fn basics() {
@@ -264,62 +221,101 @@ mod test {
     }
 
     #[test]
-    fn find_path() {
-        let mut graph = Graph::new(5, true);
+    fn counts() {
+        let mut graph = Graph::new(4, false);
         graph.insert_edge(0, 1);
-         
-        graph.insert_edge(1, 2);    
-        graph.insert_edge(1, 3);
-
-        graph.insert_edge(3, 4);
-        graph.insert_edge(3, 0);
-
-        assert_eq!(Some(vec![0, 1, 3, 4]), graph.find_path(0, 4));
-        assert_eq!(None, graph.find_path(2, 4));
+        graph.insert_edge(1, 2);
+        graph.insert_edge(2, 2);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (4, 3));
+        assert_eq!((0..4).map(|v| graph.degree(v)).collect::<Vec<_>>(), vec![1, 2, 3, 0]);
+        assert_eq!(graph.in_degree(2), graph.out_degree(2));
+
+        let mut directed = Graph::new(3, true);
+        directed.insert_edge(0, 1);
+        directed.insert_edge(0, 2);
+        directed.insert_edge(2, 1);
+        assert_eq!((directed.vertex_count(), directed.edge_count()), (3, 3));
+        assert_eq!((directed.out_degree(0), directed.in_degree(0), directed.degree(0)), (2, 0, 2));
+        assert_eq!((directed.out_degree(1), directed.in_degree(1), directed.degree(1)), (0, 2, 2));
     }
 
     #[test]
-    fn connected_components() {
-        let mut graph = Graph::new(5, true);
+    fn remove_edge() {
+        let mut graph = Graph::new(3, false);
         graph.insert_edge(0, 1);
-         
-        graph.insert_edge(1, 2);
-
-        graph.insert_edge(3, 4);
-
-        assert_eq!(2, graph.connected_components());
-
-        let graph = Graph::new(5, true);
-        assert_eq!(5, graph.connected_components());
-
-        let mut graph = Graph::new(8, true);
         graph.insert_edge(0, 1);
-        graph.insert_edge(2, 3);
-        graph.insert_edge(4, 5);
-        graph.insert_edge(6, 7);
-        assert_eq!(4, graph.connected_components());
+        graph.insert_edge(1, 1);
+        assert!(graph.remove_edge(1, 0));
+        assert!(graph.has_edge(0, 1) && graph.has_edge(1, 0));
+        assert!(graph.remove_edge(0, 1));
+        assert!(!graph.has_edge(0, 1) && !graph.has_edge(1, 0));
+        assert!(!graph.remove_edge(0, 1));
+        assert!(graph.remove_edge(1, 1));
+        assert!(!graph.has_edge(1, 1));
+        assert_eq!((graph.edge_count(), graph.degree(0), graph.degree(1)), (0, 0, 0));
+
+        let mut directed = Graph::new(2, true);
+        directed.insert_edge(0, 1);
+        assert!(!directed.remove_edge(1, 0));
+        assert!(directed.remove_edge(0, 1));
+        assert_eq!((directed.edge_count(), directed.in_degree(1)), (0, 0));
+    }
 
-        let mut graph = Graph::new(5, true);
+    #[test]
+    fn remove_vertex() {
+        let mut graph = Graph::new(4, true);
         graph.insert_edge(0, 1);
         graph.insert_edge(1, 2);
         graph.insert_edge(2, 3);
-        graph.insert_edge(3, 4);
-        assert_eq!(1, graph.connected_components());
+        graph.insert_edge(3, 1);
+        graph.insert_edge(1, 1);
+        graph.insert_edge(0, 3);
+        graph.remove_vertex(1);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (3, 2));
+        assert_eq!(graph.vertices(), 0..3);
+        // NOTE: 2 and 3 are now 1 and 2
+        assert!(graph.has_edge(1, 2) && graph.has_edge(0, 2));
+        assert_eq!((0..3).map(|v| graph.degree(v)).collect::<Vec<_>>(), vec![1, 1, 2]);
+        assert_eq!(graph.lexicographic_topological_sort(), Ok(vec![0, 1, 2]));
     }
 
     #[test]
-    fn dfs() {
-        let mut graph = Graph::new(5, true);
-        graph.insert_edge(0, 1);
-        graph.insert_edge(4, 1);
-         
-        graph.insert_edge(1, 2);    
-        graph.insert_edge(1, 3);
-
-        graph.insert_edge(3, 4);
-        graph.insert_edge(3, 0);
-        graph.find_cycle(0);
+    fn remove_random() {
+        let mut rng = XorShift(0x2545f4914f6cdd1d);
+        for directed in [false, true] {
+            let nvert = 12;
+            let mut graph = Graph::new(nvert, directed);
+            // NOTE: the same graph as an edge multiset, with vertices keeping their original labels
+            let mut labels: Vec<usize> = (0..nvert).collect();
+            let mut edges = Vec::new();
+            (0..40).for_each(|_| {
+                let (u, v) = (rng.below(nvert), rng.below(nvert));
+                graph.insert_edge(u, v);
+                edges.push((u, v));
+            });
+            while graph.vertex_count() > 1 {
+                if rng.below(3) == 0 {
+                    let v = rng.below(graph.vertex_count());
+                    graph.remove_vertex(v);
+                    let label = labels.remove(v);
+                    edges.retain(|&(a, b)| a != label && b != label);
+                } else {
+                    let (u, v) = (rng.below(graph.vertex_count()), rng.below(graph.vertex_count()));
+                    let found = edges.iter().position(|&(a, b)| (a, b) == (labels[u], labels[v]) || (!directed && (b, a) == (labels[u], labels[v])));
+                    assert_eq!(graph.remove_edge(u, v), found.is_some());
+                    if let Some(idx) = found { edges.remove(idx); }
+                }
+                assert_eq!(graph.edge_count(), edges.len());
+                graph.vertices().for_each(|v| {
+                    let label = labels[v];
+                    let out = edges.iter().filter(|&&(a, b)| a == label || (!directed && b == label)).count();
+                    let loops = edges.iter().filter(|&&(a, b)| !directed && a == label && b == label).count();
+                    let into = edges.iter().filter(|&&(_, b)| b == label).count();
+                    assert_eq!(graph.out_degree(v), out + loops);
+                    assert_eq!(graph.incident(v).count(), out + loops);
+                    if directed { assert_eq!(graph.in_degree(v), into); }
+                });
+            }
+        }
     }
 }
-
-
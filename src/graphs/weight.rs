@@ -0,0 +1,139 @@
+use std::{
+    fmt,
+    ops::{Add, Sub},
+    cmp::Ordering
+};
+
+// NOTE: anything that can label an edge. `INFINITY` marks unreachable vertices, so it must compare
+// greater than every distance an algorithm can produce
+pub trait Weight: Copy + fmt::Debug + fmt::Display + PartialEq + PartialOrd + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const INFINITY: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn saturating_add(self, other: Self) -> Self;
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {$(
+        impl Weight for $t {
+            const ZERO: Self = 0;
+            const INFINITY: Self = <$t>::MAX;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                Ord::cmp(self, other)
+            }
+        }
+    )*};
+}
+
+macro_rules! float_weight {
+    ($($t:ty),*) => {$(
+        impl Weight for $t {
+            const ZERO: Self = 0.0;
+            const INFINITY: Self = <$t>::INFINITY;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                let sum = self + other;
+                if sum.is_nan() { None } else { Some(sum) }
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                let difference = self - other;
+                if difference.is_nan() { None } else { Some(difference) }
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                self + other
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$t>::total_cmp(self, other)
+            }
+        }
+    )*};
+}
+
+integer_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_weight!(f32, f64);
+
+// NOTE: a `(weight, vertex)` heap entry ordered by the total order of the weight, so float
+// distances can go through `heap::Heap` as well
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ranked<W: Weight>(pub W, pub usize);
+
+impl<W: Weight> PartialEq for Ranked<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<W: Weight> PartialOrd for Ranked<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.0.total_cmp(&other.0).then(self.1.cmp(&other.1)))
+    }
+}
+
+// NOTE: adds two distances, treating `INFINITY` and overflow as unreachable
+pub(crate) fn extend<W: Weight>(distance: W, weight: W) -> W {
+    if distance == W::INFINITY || weight == W::INFINITY { return W::INFINITY; }
+    distance.checked_add(weight).unwrap_or(W::INFINITY)
+}
+
+// NOTE: extends a reachable distance by an edge. A sum out of range comes back as the side it left
+// on, `Greater` also covering a sum that lands on `INFINITY` and would pass for unreachable
+pub(crate) fn relax<W: Weight>(distance: W, weight: W) -> Result<W, Ordering> {
+    match distance.checked_add(weight) {
+        Some(sum) if sum != W::INFINITY => Ok(sum),
+        Some(_) => Err(Ordering::Greater),
+        None if weight < W::ZERO => Err(Ordering::Less),
+        None => Err(Ordering::Greater),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(<i32 as Weight>::checked_add(i32::MAX, 1), None);
+        assert_eq!(<i32 as Weight>::saturating_add(i32::MAX, 1), i32::MAX);
+        assert_eq!(<i32 as Weight>::checked_sub(i32::MIN, 1), None);
+        assert_eq!(<u8 as Weight>::checked_sub(3, 5), None);
+        assert_eq!(extend(i32::MAX - 1, 5), i32::INFINITY);
+        assert_eq!(extend(i32::INFINITY, -5), i32::INFINITY);
+        assert_eq!(extend(-5, i32::INFINITY), i32::INFINITY);
+        assert_eq!(extend(3u64, 4), 7);
+        assert_eq!(relax(i32::MAX - 1, 5), Err(Ordering::Greater));
+        assert_eq!(relax(i32::MAX - 1, 1), Err(Ordering::Greater));
+        assert_eq!(relax(i32::MIN + 1, -2), Err(Ordering::Less));
+        assert_eq!(relax(-5, 3), Ok(-2));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(extend(f64::INFINITY, -1.0), f64::INFINITY);
+        assert_eq!(relax(1.0, f64::INFINITY), Err(Ordering::Greater));
+        assert_eq!(relax(1.0, -2.5), Ok(-1.5));
+        assert_eq!(<f64 as Weight>::checked_add(f64::INFINITY, f64::NEG_INFINITY), None);
+        assert_eq!(<f64 as Weight>::checked_sub(f64::INFINITY, f64::INFINITY), None);
+        assert_eq!(Weight::total_cmp(&f64::NAN, &f64::INFINITY), Ordering::Greater);
+        assert!(Ranked(0.5, 3) < Ranked(1.5, 0));
+        assert!(Ranked(-0.0, 0) < Ranked(0.0, 0));
+    }
+}
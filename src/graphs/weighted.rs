@@ -2,7 +2,6 @@ use std::{
     fmt, 
     iter, 
     mem,
-    collections::HashSet,
     cmp::Ordering
 };
 
@@ -10,45 +9,75 @@ use crate::containers::{
     priority_queue::heap,
     sets::union_find
 };
+use crate::graphs::graph_like::GraphLike;
+use crate::graphs::weight::{self, Weight, Ranked};
 
 #[derive(Debug)]
-pub struct WeightedGraph {
-    edges: Vec<Option<Box<WeightedEdge>>>,
-    degrees: Vec<i32>,
+pub struct WeightedGraph<W: Weight = i32> {
+    edges: Vec<Option<Box<WeightedEdge<W>>>>,
+    // NOTE: `degrees` counts adjacency entries (out-degree when directed); `in_degrees` is only
+    // kept up to date for directed graphs
+    degrees: Vec<usize>,
+    in_degrees: Vec<usize>,
     nedges: usize,   
     nvert: usize,   
     directed: bool,   
 }
 
 #[derive(Debug)]
-struct WeightedEdge {
-    weight: i32, 
+struct WeightedEdge<W: Weight> {
+    weight: W, 
     points_to: usize, 
-    next: Option<Box<WeightedEdge>>, 
+    next: Option<Box<WeightedEdge<W>>>, 
 }
  
-impl WeightedGraph {
+impl<W: Weight> WeightedGraph<W> {
     pub fn new(vcapacity: usize, directed: bool) -> Self {
         let mut edges = Vec::with_capacity(vcapacity);
         let mut degrees = Vec::with_capacity(vcapacity);
+        let mut in_degrees = Vec::with_capacity(vcapacity);
         (0..vcapacity).for_each(|_| {
             degrees.push(0);
+            in_degrees.push(0);
             edges.push(None);
         });
         WeightedGraph {
             edges,
             degrees,
+            in_degrees,
             nedges: 0,
-            nvert: 0,
+            nvert: vcapacity,
             directed,
         }
     }
  
-    pub fn insert_edge(&mut self, i: usize, j: usize, weight: i32) {
+    // NOTE: cell (r, c) becomes vertex `r * cols + c`; cells rejected by `is_open` stay isolated
+    pub fn grid(rows: usize, cols: usize, straight: W, diagonal: Option<W>, is_open: impl Fn(usize, usize) -> bool) -> Self {
+        let mut graph = WeightedGraph::new(rows * cols, false);
+        for r in 0..rows {
+            for c in 0..cols {
+                if !is_open(r, c) { continue; }
+                if c + 1 < cols && is_open(r, c + 1) { graph.insert_edge(r * cols + c, r * cols + c + 1, straight); }
+                if r + 1 < rows && is_open(r + 1, c) { graph.insert_edge(r * cols + c, (r + 1) * cols + c, straight); }
+                if let Some(weight) = diagonal {
+                    if r + 1 < rows && c + 1 < cols && is_open(r + 1, c + 1) {
+                        graph.insert_edge(r * cols + c, (r + 1) * cols + c + 1, weight);
+                    }
+                    if r + 1 < rows && c > 0 && is_open(r + 1, c - 1) {
+                        graph.insert_edge(r * cols + c, (r + 1) * cols + c - 1, weight);
+                    }
+                }
+            }
+        }
+        graph
+    }
+ 
+    // NOTE: an undirected self-loop adds 2 to the degree of its vertex
+    pub fn insert_edge(&mut self, i: usize, j: usize, weight: W) {
         assert!(j < self.edges.len() && i < self.edges.len(), "vertices `i` and `j` must be within capacity");
         self.nedges += 1;
-        self.nvert += 1;
         self.degrees[i] += 1;
+        if self.directed { self.in_degrees[j] += 1 } else { self.degrees[j] += 1 }
         match mem::replace(&mut self.edges[i], None) {
             None => self.edges[i] = Some(Box::new(WeightedEdge::new(weight, j, None))),
             edge => self.edges[i] = Some(Box::new(WeightedEdge::new(weight, j, edge))),
@@ -62,159 +91,667 @@ impl WeightedGraph {
         }
     }
 
-    pub fn prims<'a>(&'a self, start: usize) -> MinSpanTree<'a> {
-        let mut distance: Vec<i32> = Vec::with_capacity(self.edges.len());
+    // NOTE: removes a single `i -> j` edge (both of its entries when undirected), the most
+    // recently inserted one if there are parallel edges. Returns its weight if there was one
+    pub fn remove_edge(&mut self, i: usize, j: usize) -> Option<W> {
+        assert!(j < self.edges.len() && i < self.edges.len(), "vertices `i` and `j` must be within capacity");
+        let weight = unlink(&mut self.edges[i], |edge| edge.points_to == j)?;
+        if !self.directed { unlink(&mut self.edges[j], |edge| edge.points_to == i && edge.weight == weight); }
+        self.nedges -= 1;
+        self.degrees[i] -= 1;
+        if self.directed { self.in_degrees[j] -= 1 } else { self.degrees[j] -= 1 }
+        Some(weight)
+    }
+
+    // NOTE: drops `v` with all of its edges and compacts the ids, every vertex above `v` moving
+    // down by one so the vertices stay `0..vertex_count()`
+    pub fn remove_vertex(&mut self, v: usize) {
+        assert!(v < self.edges.len(), "vertex `v` must be within capacity");
+        let outgoing: Vec<usize> = self.incident(v).map(|(u, _)| u).collect();
+        outgoing.into_iter().for_each(|u| { self.remove_edge(v, u); });
+        if self.directed {
+            let incoming: Vec<usize> = (0..self.edges.len()).filter(|&u| self.incident(u).any(|(w, _)| w == v)).collect();
+            incoming.into_iter().for_each(|u| while self.remove_edge(u, v).is_some() {});
+        }
+        self.edges.remove(v);
+        self.degrees.remove(v);
+        self.in_degrees.remove(v);
+        self.nvert -= 1;
+        self.edges.iter_mut().for_each(|list| {
+            let mut cur_edge = list.as_deref_mut();
+            while let Some(edge) = cur_edge {
+                if edge.points_to > v { edge.points_to -= 1; }
+                cur_edge = edge.next.as_deref_mut();
+            }
+        });
+    }
+
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.incident(i).any(|(v, _)| v == j)
+    }
+
+    // NOTE: in- plus out-degree when directed
+    pub fn degree(&self, v: usize) -> usize {
+        if self.directed { self.degrees[v] + self.in_degrees[v] } else { self.degrees[v] }
+    }
+
+    pub fn in_degree(&self, v: usize) -> usize {
+        if self.directed { self.in_degrees[v] } else { self.degrees[v] }
+    }
+
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.degrees[v]
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.nvert
+    }
+
+    // NOTE: an undirected edge counts once even though it is stored at both endpoints
+    pub fn edge_count(&self) -> usize {
+        self.nedges
+    }
+
+    pub(crate) fn vcapacity(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub(crate) fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub(crate) fn incident(&self, v: usize) -> Incident<'_, W> {
+        Incident { cur_edge: &self.edges[v] }
+    }
+
+    // NOTE: when the component of `start` is exhausted the search restarts from the closest
+    // remaining (unreachable) vertex, so disconnected graphs give a minimum spanning forest
+    pub fn prims<'a>(&'a self, start: usize) -> Result<MinSpanTree<'a, W>, SpanningTreeError> {
+        if self.directed { return Err(SpanningTreeError::Directed); }
+        let mut distance: Vec<W> = Vec::with_capacity(self.edges.len());
         let mut intree: Vec<bool> = Vec::with_capacity(self.edges.len());
         let mut parent: Vec<Option<usize>> = Vec::with_capacity(self.edges.len());
-        let mut weight = 0;
+        let mut tree_edges = Vec::new();
         (0..self.edges.len()).for_each(|_| {
-            distance.push(i32::MAX);
+            distance.push(W::INFINITY);
             parent.push(None);
             intree.push(false);
         });
-        distance[start] = 0;
+        distance[start] = W::ZERO;
         let mut cur_vertex = start;
         while !intree[cur_vertex] {
             intree[cur_vertex] = true;
-            if cur_vertex != start { weight += distance[cur_vertex] } 
+            if let Some(p) = parent[cur_vertex] { tree_edges.push((p, cur_vertex, distance[cur_vertex])); }
             // NOTE: first, only look at neighbors and update if neighbor weight is less than
             // current smallest 
-            let mut cur_edge = &self.edges[cur_vertex];
-            while let Some(edge) = cur_edge.as_deref() {
-                if distance[edge.points_to] > edge.weight { 
-                    distance[edge.points_to] = edge.weight;
-                    parent[edge.points_to] = Some(cur_vertex);
+            for (v, weight) in self.incident(cur_vertex) {
+                if !intree[v] && distance[v] > weight { 
+                    distance[v] = weight;
+                    parent[v] = Some(cur_vertex);
                 }
-                cur_edge = &edge.next;
             }
             // NOTE: choose the closest vertex NOT in our tree (where closest is guaranteed to
             // exist as we redefine all distances for immediate neighbors)
-            let temp = match iter::zip(distance.iter(), intree.iter()).enumerate().filter(|(_, (_, &intree))| !intree).map(|(idx, (d, _))| (idx, d)).min_by_key(|(_, &d)| d) {
+            let temp = match iter::zip(distance.iter(), intree.iter()).enumerate().filter(|(_, (_, &intree))| !intree).map(|(idx, (d, _))| (idx, d)).min_by(|(_, a), (_, b)| a.total_cmp(b)) {
                 None => break,
                 Some(min) => min.0,
             };
             cur_vertex = temp;
         }
-        MinSpanTree::new(self, parent, weight)
+        Ok(MinSpanTree::new(self, tree_edges))
     }
 
-    pub fn kruskals<'a>(&'a self) -> MinSpanTree<'a> {
-        let mut parent: Vec<Option<usize>> = Vec::with_capacity(self.edges.len());
-        let mut weight = 0;
-        (0..self.edges.len()).for_each(|_| {
-            parent.push(None);
-        });
-
-        let mut queue: heap::Heap<EdgePair> = heap::Heap::new();
-        self.edges.iter().enumerate().for_each(|(idx, edge_list)| {
-            edge_list.as_deref().map(|edge| {
-                queue.insert(EdgePair::new(idx, edge.points_to, edge.weight));
-                    
-                let mut cur_edge = &edge.next;
-                while let Some(e) = cur_edge {
-                    queue.insert(EdgePair::new(idx, e.points_to, e.weight));
-                    cur_edge = &e.next;
-                }
-            });
-        });
+    pub fn kruskals<'a>(&'a self) -> Result<MinSpanTree<'a, W>, SpanningTreeError> {
+        if self.directed { return Err(SpanningTreeError::Directed); }
+        let mut tree_edges = Vec::new();
+        let mut queue: heap::Heap<EdgePair<W>> = heap::Heap::new();
+        self.edge_pairs().into_iter().for_each(|edge| queue.insert(edge));
 
-        let mut set = union_find::UnionFind::new(queue.len());
+        let mut set = union_find::UnionFind::new(self.edges.len());
         while let Some(edge) = queue.pop() {
-            if !(set.find(edge.source) == set.find(edge.points_to)) {
-                parent[edge.points_to] = Some(edge.source);
-                weight += edge.weight;
+            if set.find(edge.source) != set.find(edge.points_to) {
+                tree_edges.push((edge.source, edge.points_to, edge.weight));
                 set.union(edge.source, edge.points_to);
             }
         }
-        MinSpanTree::new(self, parent, weight)
+        Ok(MinSpanTree::new(self, tree_edges))
     }
 
-    pub fn dijkstras<'a>(&'a self, start: usize) -> ShortestPaths<'a> {
-        let mut distance: Vec<i32> = Vec::with_capacity(self.edges.len());
-        let mut intree: Vec<bool> = Vec::with_capacity(self.edges.len());
-        let mut parent: Vec<Option<usize>> = Vec::with_capacity(self.edges.len());
-        (0..self.edges.len()).for_each(|_| {
-            distance.push(i32::MAX);
-            parent.push(None);
-            intree.push(false);
+    // NOTE: every round picks the cheapest edge leaving each component independently of the
+    // others, which is what makes the rounds easy to parallelize
+    pub fn boruvkas<'a>(&'a self) -> Result<MinSpanTree<'a, W>, SpanningTreeError> {
+        if self.directed { return Err(SpanningTreeError::Directed); }
+        let mut tree_edges = Vec::new();
+        let edges = self.edge_pairs();
+        let mut set = union_find::UnionFind::new(self.edges.len());
+        loop {
+            let mut cheapest: Vec<Option<EdgePair<W>>> = vec![None; self.edges.len()];
+            for &edge in &edges {
+                let (x, y) = (set.find(edge.source), set.find(edge.points_to));
+                if x == y { continue; }
+                [x, y].iter().for_each(|&root| {
+                    if cheapest[root].is_none_or(|best| edge.key_cmp(&best) == Ordering::Less) { cheapest[root] = Some(edge); }
+                });
+            }
+
+            let mut merged = false;
+            // NOTE: ties are broken by endpoints so the chosen edges can never close a cycle
+            for edge in cheapest.into_iter().flatten() {
+                if set.find(edge.source) != set.find(edge.points_to) {
+                    tree_edges.push((edge.source, edge.points_to, edge.weight));
+                    set.union(edge.source, edge.points_to);
+                    merged = true;
+                }
+            }
+            if !merged { break; }
+        }
+        Ok(MinSpanTree::new(self, tree_edges))
+    }
+
+    // NOTE: drop edges from heaviest to lightest unless doing so would disconnect their endpoints
+    pub fn reverse_delete<'a>(&'a self) -> Result<MinSpanTree<'a, W>, SpanningTreeError> {
+        if self.directed { return Err(SpanningTreeError::Directed); }
+        let mut edges = self.edge_pairs();
+        edges.sort_by(|a, b| a.key_cmp(b));
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); self.edges.len()];
+        edges.iter().enumerate().for_each(|(idx, edge)| {
+            adjacency[edge.source].push(idx);
+            adjacency[edge.points_to].push(idx);
         });
-        distance[start] = 0;
-        let mut cur_vertex = start;
-        while !intree[cur_vertex] {
-            intree[cur_vertex] = true;
-            let mut adj_v = &self.edges[cur_vertex];
-            while let Some(edge) = adj_v.as_deref() {
-                assert!(edge.weight > 0, "Dijkstra's algorithm does not work for graphs with negative weights");
-                if distance[edge.points_to] > distance[cur_vertex] + edge.weight { 
-                    distance[edge.points_to] = distance[cur_vertex] + edge.weight;
-                    parent[edge.points_to] = Some(cur_vertex);
+
+        let mut kept = vec![true; edges.len()];
+        for idx in (0..edges.len()).rev() {
+            kept[idx] = false;
+            let (start, end) = (edges[idx].source, edges[idx].points_to);
+            let mut discovered = vec![false; self.edges.len()];
+            let mut stack = vec![start];
+            discovered[start] = true;
+            while let Some(v) = stack.pop() {
+                for &e in adjacency[v].iter().filter(|&&e| kept[e]) {
+                    let u = if edges[e].source == v { edges[e].points_to } else { edges[e].source };
+                    if !discovered[u] {
+                        discovered[u] = true;
+                        stack.push(u);
+                    }
                 }
-                adj_v = &edge.next;
             }
-            cur_vertex = match iter::zip(distance.iter(), intree.iter())
-                .enumerate()
-                .filter(|(_, (_, &intree))| !intree)
-                .map(|(idx, (d, _))| (idx, d))
-                .min_by_key(|(_, &d)| d) {
-                None => break,
-                Some(min) => min.0,
+            if !discovered[end] { kept[idx] = true; }
+        }
+
+        let tree_edges = edges.iter().zip(kept.iter())
+            .filter(|(_, &kept)| kept)
+            .map(|(edge, _)| (edge.source, edge.points_to, edge.weight))
+            .collect();
+        Ok(MinSpanTree::new(self, tree_edges))
+    }
+
+    fn edge_pairs(&self) -> Vec<EdgePair<W>> {
+        let mut pairs = Vec::new();
+        (0..self.edges.len()).for_each(|u| self.incident(u).for_each(|(v, weight)| {
+            // NOTE: undirected edges are stored in both adjacency lists, only keep them once
+            if self.directed || u <= v { pairs.push(EdgePair::new(u, v, weight)); }
+        }));
+        pairs
+    }
+}
+
+// NOTE: Dijkstra on the graph without the vertices flagged in `excluded_vertices` and without the
+// edges from `start` to the vertices flagged in `excluded_targets` (an empty slice excludes none)
+pub(crate) fn dijkstras_excluding<'a, W: Weight, G: GraphLike<Weight = W>>(
+    graph: &'a G, 
+    start: usize, 
+    excluded_vertices: &[bool], 
+    excluded_targets: &[bool]
+) -> Result<ShortestPaths<'a, W, G>, PathError> {
+    let nvert = graph.vertices().len();
+    let is_flagged = |mask: &[bool], v: usize| mask.get(v).copied().unwrap_or(false);
+    let mut distance: Vec<W> = Vec::with_capacity(nvert);
+    let mut intree: Vec<bool> = Vec::with_capacity(nvert);
+    let mut parent: Vec<Option<usize>> = Vec::with_capacity(nvert);
+    (0..nvert).for_each(|_| {
+        distance.push(W::INFINITY);
+        parent.push(None);
+        intree.push(false);
+    });
+    distance[start] = W::ZERO;
+    let mut beyond: Vec<Option<usize>> = vec![None; nvert];
+    let mut queue: heap::Heap<Ranked<W>> = heap::Heap::new();
+    queue.insert(Ranked(W::ZERO, start));
+    while let Some(Ranked(_, cur_vertex)) = queue.pop() {
+        // NOTE: a vertex is pushed again every time its distance improves, so skip the stale
+        // entries of vertices that were already settled
+        if intree[cur_vertex] { continue; }
+        intree[cur_vertex] = true;
+        for (v, weight) in graph.weighted_neighbors(cur_vertex) {
+            if weight < W::ZERO { return Err(PathError::NegativeEdge(cur_vertex, v)); }
+            if is_flagged(excluded_vertices, v) || (cur_vertex == start && is_flagged(excluded_targets, v)) { continue; }
+            let candidate = match weight::relax(distance[cur_vertex], weight) {
+                Ok(candidate) => candidate,
+                Err(_) => { beyond[v] = Some(cur_vertex); continue; },
+            };
+            if distance[v] > candidate { 
+                distance[v] = candidate;
+                parent[v] = Some(cur_vertex);
+                queue.insert(Ranked(candidate, v));
+            }
+        }
+    }
+    check_beyond(&distance, &beyond)?;
+    Ok(ShortestPaths::new(graph, start, parent, distance))
+}
+
+pub(crate) fn bellman_ford<W: Weight, G: GraphLike<Weight = W>>(graph: &G, start: usize) -> Result<ShortestPaths<'_, W, G>, PathError> {
+    let nvert = graph.vertices().len();
+    let mut distance: Vec<W> = Vec::with_capacity(nvert);
+    let mut parent: Vec<Option<usize>> = Vec::with_capacity(nvert);
+    (0..nvert).for_each(|_| {
+        distance.push(W::INFINITY);
+        parent.push(None);
+    });
+    distance[start] = W::ZERO;
+    let mut beyond: Vec<Option<usize>> = vec![None; nvert];
+    // NOTE: without negative cycles every shortest path has at most V-1 edges, so anything
+    // still relaxing on the V-th pass proves a negative cycle is reachable from `start`
+    let mut last_relaxed = None;
+    for _ in 0..nvert {
+        last_relaxed = None;
+        for cur_vertex in 0..nvert {
+            if distance[cur_vertex] == W::INFINITY { continue; }
+            for (v, weight) in graph.weighted_neighbors(cur_vertex) {
+                let candidate = match weight::relax(distance[cur_vertex], weight) {
+                    Ok(candidate) => candidate,
+                    Err(Ordering::Less) => return Err(PathError::Overflow(cur_vertex, v)),
+                    Err(_) => { beyond[v] = Some(cur_vertex); continue; },
+                };
+                if distance[v] > candidate { 
+                    distance[v] = candidate;
+                    parent[v] = Some(cur_vertex);
+                    last_relaxed = Some(v);
+                }
+            }
+        }
+        if last_relaxed.is_none() { break; }
+    }
+
+    match last_relaxed {
+        None => {
+            check_beyond(&distance, &beyond)?;
+            Ok(ShortestPaths::new(graph, start, parent, distance))
+        },
+        Some(relaxed) => {
+            // NOTE: walking V parents back from a vertex relaxed on the last pass is
+            // guaranteed to land on the cycle itself rather than on a path leading into it
+            let mut on_cycle = relaxed;
+            (0..nvert).for_each(|_| {
+                on_cycle = parent[on_cycle].expect("relaxed vertices always have a parent");
+            });
+            let mut cycle = vec![on_cycle];
+            let mut cur_vertex = parent[on_cycle].expect("relaxed vertices always have a parent");
+            while cur_vertex != on_cycle {
+                cycle.push(cur_vertex);
+                cur_vertex = parent[cur_vertex].expect("relaxed vertices always have a parent");
+            }
+            cycle.reverse();
+            Err(PathError::NegativeCycle(cycle))
+        },
+    }
+}
+
+pub(crate) fn a_star<W: Weight, G: GraphLike<Weight = W>>(
+    graph: &G, 
+    start: usize, 
+    goal: usize, 
+    heuristic: impl Fn(usize) -> W
+) -> Result<AStarSearch<W>, PathError> {
+    let nvert = graph.vertices().len();
+    let mut distance: Vec<W> = Vec::with_capacity(nvert);
+    let mut intree: Vec<bool> = Vec::with_capacity(nvert);
+    let mut parent: Vec<Option<usize>> = Vec::with_capacity(nvert);
+    (0..nvert).for_each(|_| {
+        distance.push(W::INFINITY);
+        parent.push(None);
+        intree.push(false);
+    });
+    distance[start] = W::ZERO;
+    let mut beyond: Vec<Option<usize>> = vec![None; nvert];
+    let mut expanded = 0;
+    let mut queue: heap::Heap<Ranked<W>> = heap::Heap::new();
+    queue.insert(Ranked(heuristic(start), start));
+    while let Some(Ranked(_, cur_vertex)) = queue.pop() {
+        if intree[cur_vertex] { continue; }
+        intree[cur_vertex] = true;
+        if cur_vertex == goal { break; }
+        expanded += 1;
+        let cur_estimate = heuristic(cur_vertex);
+        for (v, weight) in graph.weighted_neighbors(cur_vertex) {
+            if weight < W::ZERO { return Err(PathError::NegativeEdge(cur_vertex, v)); }
+            debug_assert!(
+                cur_estimate <= weight.saturating_add(heuristic(v)), 
+                "heuristic is inconsistent on edge {} -> {}", cur_vertex, v
+            );
+            let candidate = match weight::relax(distance[cur_vertex], weight) {
+                Ok(candidate) => candidate,
+                Err(_) => { beyond[v] = Some(cur_vertex); continue; },
             };
+            if distance[v] > candidate { 
+                distance[v] = candidate;
+                parent[v] = Some(cur_vertex);
+                queue.insert(Ranked(candidate.saturating_add(heuristic(v)), v));
+            }
         }
-        ShortestPaths::new(self, start, parent, distance)
     }
+
+    if !intree[goal] {
+        check_beyond(&distance, &beyond)?;
+        return Ok((None, expanded));
+    }
+    let mut path = vec![goal];
+    let mut cur_vertex = goal;
+    while let Some(adj_v) = parent[cur_vertex] {
+        path.push(adj_v);
+        cur_vertex = adj_v;
+    }
+    path.reverse();
+    Ok((Some(Path::new(path, distance[goal])), expanded))
+}
+
+// NOTE: a vertex only reached by relaxations past `INFINITY` is out of range rather than
+// unreachable. `beyond[v]` is the vertex one of them came from
+pub(crate) fn check_beyond<W: Weight>(distance: &[W], beyond: &[Option<usize>]) -> Result<(), PathError> {
+    let out_of_range = (0..distance.len())
+        .find_map(|v| beyond[v].filter(|_| distance[v] == W::INFINITY).map(|u| (u, v)));
+    match out_of_range {
+        Some((u, v)) => Err(PathError::Overflow(u, v)),
+        None => Ok(()),
+    }
+}
+
+// NOTE: unlinks the first entry of `list` accepted by `matches`, returning its weight
+fn unlink<W: Weight>(mut list: &mut Option<Box<WeightedEdge<W>>>, matches: impl Fn(&WeightedEdge<W>) -> bool) -> Option<W> {
+    while list.as_deref().is_some_and(|edge| !matches(edge)) {
+        list = &mut list.as_mut().expect("checked by the loop condition").next;
+    }
+    let edge = list.take()?;
+    *list = edge.next;
+    Some(edge.weight)
+}
+
+// NOTE: the `(points_to, weight)` pairs of an adjacency list, which is what `GraphLike` hands out
+// as weighted neighbors
+pub struct Incident<'a, W: Weight> {
+    cur_edge: &'a Option<Box<WeightedEdge<W>>>,
 }
 
-// TODO: see if I can define a function to go through all incident vertices
-// let mut adj_v = &self.edges[cur_vertex];
-// while let Some(edge) = adj_v.as_deref() {
-//     if distance[edge.points_to] > distance[cur_vertex] + edge.weight { 
-//         distance[edge.points_to] = distance[cur_vertex] + edge.weight;
-//         parent[edge.points_to] = Some(cur_vertex);
-//     }
-//     adj_v = &edge.next;
-// }
+impl<'a, W: Weight> Iterator for Incident<'a, W> {
+    type Item = (usize, W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge = self.cur_edge.as_deref()?;
+        self.cur_edge = &edge.next;
+        Some((edge.points_to, edge.weight))
+    }
+}
 
 #[derive(Copy, Clone, Debug)]
-struct EdgePair {
+struct EdgePair<W: Weight> {
     source: usize,
     points_to: usize,
-    weight: i32,
+    weight: W,
 }  
 
-impl EdgePair {
-    fn new(source: usize, points_to: usize, weight: i32) -> Self {
+impl<W: Weight> EdgePair<W> {
+    fn new(source: usize, points_to: usize, weight: W) -> Self {
         EdgePair{ source, points_to, weight}
     }
+
+    // NOTE: orders by weight, breaking ties by endpoints so that every edge has a distinct rank
+    fn key_cmp(&self, other: &Self) -> Ordering {
+        self.weight.total_cmp(&other.weight)
+            .then(self.source.min(self.points_to).cmp(&other.source.min(other.points_to)))
+            .then(self.source.max(self.points_to).cmp(&other.source.max(other.points_to)))
+    }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum PathError {
+    NegativeEdge(usize, usize),
+    NegativeCycle(Vec<usize>),
+    Overflow(usize, usize),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SpanningTreeError {
+    Directed,
+    Unreachable(usize),
+    MissingEdge(usize, usize),
+    Cycle(usize, usize),
+    NotSpanning(usize, usize),
+    NotMinimum(usize, usize),
+    Overflow(usize, usize),
+}
+
+// NOTE: the vertices of one tree in a spanning forest along with its `(u, v, weight)` edges
+pub type Tree<W = i32> = (Vec<usize>, Vec<(usize, usize, W)>);
+
 #[derive(Debug)]
-pub struct MinSpanTree<'a> {
-    graph: &'a WeightedGraph,
+pub struct MinSpanTree<'a, W: Weight = i32> {
+    graph: &'a WeightedGraph<W>,
+    // NOTE: each component is rooted at its smallest vertex, which is the only one without a parent
     parents: Vec<Option<usize>>,
-    total_weight: i32,
+    edges: Vec<(usize, usize, W)>,
+    component: Vec<usize>,
+    ncomponents: usize,
+    total_weight: W,
+}
+
+// NOTE: binary lifting over a rooted forest: `ancestor[k][v]` is the vertex `2^k` levels above `v`
+// (or its root) and `heaviest[k][v]` the heaviest edge on the way there, so the heaviest edge on
+// the tree path between two vertices takes O(log V) steps to find
+struct Lifting<W: Weight> {
+    depth: Vec<usize>,
+    ancestor: Vec<Vec<usize>>,
+    heaviest: Vec<Vec<Option<W>>>,
+}
+
+impl<'a, W: Weight> MinSpanTree<'a, W> {
+    pub fn new(graph: &'a WeightedGraph<W>, edges: Vec<(usize, usize, W)>) -> Self {
+        let nvert = graph.edges.len();
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); nvert];
+        edges.iter().for_each(|&(u, v, _)| {
+            adjacency[u].push(v);
+            adjacency[v].push(u);
+        });
+
+        let mut parents: Vec<Option<usize>> = vec![None; nvert];
+        let mut component: Vec<Option<usize>> = vec![None; nvert];
+        let mut ncomponents = 0;
+        for root in 0..nvert {
+            if component[root].is_some() { continue; }
+            component[root] = Some(ncomponents);
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for &u in &adjacency[v] {
+                    if component[u].is_none() {
+                        component[u] = Some(ncomponents);
+                        parents[u] = Some(v);
+                        stack.push(u);
+                    }
+                }
+            }
+            ncomponents += 1;
+        }
+        let component = component.into_iter().map(|c| c.expect("every vertex is visited")).collect();
+        let total_weight = edges.iter().fold(W::ZERO, |total, &(_, _, weight)| total.saturating_add(weight));
+        MinSpanTree { graph, parents, edges, component, ncomponents, total_weight }
+    }
+
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.ncomponents
+    }
+
+    pub fn component_of(&self, v: usize) -> usize {
+        self.component[v]
+    }
+
+    // NOTE: checks that this is a spanning forest of `graph` and that it satisfies the cycle
+    // property: no graph edge is lighter than the heaviest tree edge on the path it would close
+    pub fn verify(&self, graph: &WeightedGraph<W>) -> Result<(), SpanningTreeError> {
+        let nvert = graph.edges.len();
+        let mut set = union_find::UnionFind::new(nvert);
+        let mut parent_weight: Vec<Option<W>> = vec![None; nvert];
+        for &(u, v, weight) in &self.edges {
+            if !graph.incident(u).any(|edge| edge == (v, weight)) { return Err(SpanningTreeError::MissingEdge(u, v)); }
+            if set.find(u) == set.find(v) { return Err(SpanningTreeError::Cycle(u, v)); }
+            set.union(u, v);
+            if self.parents[v] == Some(u) { parent_weight[v] = Some(weight); } else { parent_weight[u] = Some(weight); }
+        }
+
+        let lifting = Lifting::new(&self.parents, &parent_weight);
+        for u in 0..nvert {
+            for (v, weight) in graph.incident(u) {
+                if self.component[u] != self.component[v] { return Err(SpanningTreeError::NotSpanning(u, v)); }
+                if lifting.heaviest_between(u, v).is_some_and(|h| h.total_cmp(&weight) == Ordering::Greater) {
+                    return Err(SpanningTreeError::NotMinimum(u, v));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // NOTE: the vertices and edges of every tree in the forest, indexed by component
+    pub fn trees(&self) -> Vec<Tree<W>> {
+        let mut trees: Vec<Tree<W>> = vec![(Vec::new(), Vec::new()); self.ncomponents];
+        self.component.iter().enumerate().for_each(|(v, &c)| trees[c].0.push(v));
+        self.edges.iter().for_each(|&edge| trees[self.component[edge.0]].1.push(edge));
+        trees
+    }
 }
 
-impl<'a> MinSpanTree<'a> {
-    pub fn new(graph: &'a WeightedGraph, parents: Vec<Option<usize>>, total_weight: i32) -> Self {
-        MinSpanTree { graph, parents, total_weight}
+impl<W: Weight> Lifting<W> {
+    // NOTE: `parent_weight[v]` is the weight of the edge from `v` to `parents[v]`
+    fn new(parents: &[Option<usize>], parent_weight: &[Option<W>]) -> Self {
+        let nvert = parents.len();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); nvert];
+        (0..nvert).for_each(|v| if let Some(p) = parents[v] { children[p].push(v) });
+        let mut depth = vec![0; nvert];
+        let mut stack: Vec<usize> = (0..nvert).filter(|&v| parents[v].is_none()).collect();
+        while let Some(v) = stack.pop() {
+            children[v].iter().for_each(|&u| {
+                depth[u] = depth[v] + 1;
+                stack.push(u);
+            });
+        }
+
+        let levels = (usize::BITS - nvert.leading_zeros()).max(1) as usize;
+        let mut ancestor = Vec::with_capacity(levels);
+        let mut heaviest = Vec::with_capacity(levels);
+        ancestor.push((0..nvert).map(|v| parents[v].unwrap_or(v)).collect::<Vec<_>>());
+        heaviest.push(parent_weight.to_vec());
+        for k in 1..levels {
+            let (up, max): (&Vec<usize>, &Vec<Option<W>>) = (&ancestor[k - 1], &heaviest[k - 1]);
+            let next_max = (0..nvert).map(|v| Self::heavier(max[v], max[up[v]])).collect();
+            let next_up = (0..nvert).map(|v| up[up[v]]).collect();
+            ancestor.push(next_up);
+            heaviest.push(next_max);
+        }
+        Lifting { depth, ancestor, heaviest }
     }
 
+    fn heavier(a: Option<W>, b: Option<W>) -> Option<W> {
+        match (a, b) {
+            (Some(a), Some(b)) if a.total_cmp(&b) == Ordering::Less => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        }
+    }
+
+    // NOTE: `u` and `v` must lie in the same tree. Lift the deeper one to the depth of the other,
+    // then both to just below their lowest common ancestor
+    fn heaviest_between(&self, mut u: usize, mut v: usize) -> Option<W> {
+        if self.depth[u] < self.depth[v] { mem::swap(&mut u, &mut v); }
+        let mut heaviest = None;
+        let diff = self.depth[u] - self.depth[v];
+        for k in (0..self.ancestor.len()).filter(|&k| diff >> k & 1 == 1) {
+            heaviest = Self::heavier(heaviest, self.heaviest[k][u]);
+            u = self.ancestor[k][u];
+        }
+        if u == v { return heaviest; }
+        for k in (0..self.ancestor.len()).rev() {
+            if self.ancestor[k][u] != self.ancestor[k][v] {
+                heaviest = Self::heavier(heaviest, Self::heavier(self.heaviest[k][u], self.heaviest[k][v]));
+                u = self.ancestor[k][u];
+                v = self.ancestor[k][v];
+            }
+        }
+        Self::heavier(heaviest, Self::heavier(self.heaviest[0][u], self.heaviest[0][v]))
+    }
 }
 
 #[derive(Debug)]
-pub struct ShortestPaths<'a> {
-    graph: &'a WeightedGraph,
+pub struct ShortestPaths<'a, W: Weight = i32, G = WeightedGraph<W>> {
+    graph: &'a G,
     start: usize,
-    parents: Vec<Option<usize>>,
-    distance: Vec<i32>,
+    pub(crate) parents: Vec<Option<usize>>,
+    pub(crate) distance: Vec<W>,
 }
 
-impl<'a> ShortestPaths<'a> {
-    pub fn new(graph: &'a WeightedGraph, start: usize, parents: Vec<Option<usize>>, distance: Vec<i32>) -> Self {
+impl<'a, W: Weight, G: GraphLike<Weight = W>> ShortestPaths<'a, W, G> {
+    pub fn new(graph: &'a G, start: usize, parents: Vec<Option<usize>>, distance: Vec<W>) -> Self {
         ShortestPaths { graph, start, parents, distance }
     }
 
-    pub fn path_to(&self, end: usize) -> Option<Path> {  
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn distance_to(&self, v: usize) -> Option<W> {
+        if self.is_reachable(v) { Some(self.distance[v]) } else { None }
+    }
+
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.distance[v] != W::INFINITY
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn iter_reachable(&self) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.distance.iter().enumerate().filter(|(_, &d)| d != W::INFINITY).map(|(v, &d)| (v, d))
+    }
+
+    // NOTE: the shortest path tree as a directed graph with the same vertices, where every reachable
+    // vertex gets a single incoming edge from its parent
+    pub fn shortest_path_tree(&self) -> WeightedGraph<W> {
+        let mut tree = WeightedGraph::new(self.parents.len(), true);
+        self.parents.iter().enumerate().for_each(|(v, parent)| {
+            if let Some(p) = *parent {
+                // NOTE: with parallel edges the lightest one is the edge that was relaxed
+                let weight = self.graph.weighted_neighbors(p)
+                    .filter(|&(u, _)| u == v)
+                    .map(|(_, weight)| weight)
+                    .min_by(|a, b| a.total_cmp(b))
+                    .expect("tree edges come from the graph");
+                tree.insert_edge(p, v, weight);
+            }
+        });
+        tree
+    }
+
+    pub fn path_to(&self, end: usize) -> Option<Path<W>> {  
+        if end == self.start { return Some(Path::new(vec![end], W::ZERO)); }
         let mut cur_vertex = self.parents[end];
         let mut path = Vec::new();
         path.insert(0, end);
@@ -227,21 +764,45 @@ impl<'a> ShortestPaths<'a> {
     }
 }
 
+// NOTE: the path found by A* (if the goal is reachable) and the number of expanded vertices
+pub type AStarSearch<W> = (Option<Path<W>>, usize);
+
 #[derive(Debug)]
-pub struct Path {
-    path: Vec<usize>,
-    weight: i32,
+pub struct Path<W: Weight = i32> {
+    pub(crate) path: Vec<usize>,
+    pub(crate) weight: W,
 }
 
-impl Path {
-    pub fn new(path: Vec<usize>, weight: i32) -> Self {
+impl<W: Weight> Path<W> {
+    pub fn new(path: Vec<usize>, weight: W) -> Self {
         Path { path, weight }
     }
+
+    pub fn vertices(&self) -> &[usize] {
+        &self.path
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.path.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    pub fn weight(&self) -> W {
+        self.weight
+    }
+
+    // NOTE: the number of edges, so a path made of just its start has length 0
+    pub fn len(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 
-impl WeightedEdge {
-    fn new(weight: i32, points_to: usize, next: Option<Box<WeightedEdge>>) -> Self { 
+impl<W: Weight> WeightedEdge<W> {
+    fn new(weight: W, points_to: usize, next: Option<Box<WeightedEdge<W>>>) -> Self { 
         WeightedEdge {
             weight,
             points_to,
@@ -250,7 +811,7 @@ impl WeightedEdge {
     }
 }
 
-impl fmt::Display for WeightedEdge {
+impl<W: Weight> fmt::Display for WeightedEdge<W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}[w:{}] ", self.points_to, self.weight)?;
         let mut cur_edge = &self.next;
@@ -262,63 +823,94 @@ impl fmt::Display for WeightedEdge {
     }
 }
 
-impl fmt::Display for WeightedGraph {
+impl<W: Weight> fmt::Display for Path<W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.edges.iter().enumerate().for_each(|(i, maybe_edge)| {
-            maybe_edge.as_ref().map(|edge| writeln!(f, "{}: {}", i, edge).expect("writer out of memory"));
-            ()
-        });
-        Ok(())
+        let mut vertices = self.path.iter();
+        if let Some(first) = vertices.next() { write!(f, "{}", first)?; }
+        vertices.try_for_each(|v| write!(f, " -> {}", v))?;
+        write!(f, " [w:{}]", self.weight)
     }
 }
-    
 
-impl PartialEq for EdgePair {
-    fn eq(&self, other: &Self) -> bool {
-        self.weight == other.weight && self.source == other.source && self.points_to == other.points_to    
+impl<'a, W: Weight, G: GraphLike<Weight = W>> fmt::Display for ShortestPaths<'a, W, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (v, d) in self.iter_reachable() {
+            match self.parents[v] {
+                None => writeln!(f, "{}: [w:{}]", v, d)?,
+                Some(p) => writeln!(f, "{}: [w:{}] via {}", v, d, p)?,
+            }
+        }
+        Ok(())
     }
+}
 
-    fn ne(&self, other: &Self) -> bool {
-        !(self.weight == other.weight && self.source == other.source && self.points_to == other.points_to)
+impl<'a, W: Weight> fmt::Display for MinSpanTree<'a, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (u, v, weight) in &self.edges {
+            writeln!(f, "{} - {}[w:{}]", u, v, weight)?;
+        }
+        writeln!(f, "total[w:{}] components: {}", self.total_weight, self.ncomponents)
     }
 }
 
-impl PartialOrd for EdgePair {
-    fn lt(&self, other: &Self) -> bool {
-        self.weight < other.weight    
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::NegativeEdge(i, j) => write!(f, "negative edge weight on {} -> {}", i, j),
+            PathError::Overflow(i, j) => write!(f, "weights overflow along {} -> {}", i, j),
+            PathError::NegativeCycle(cycle) => {
+                write!(f, "negative cycle:")?;
+                cycle.iter().try_for_each(|v| write!(f, " {}", v))
+            },
+        }
     }
+}
+
+impl std::error::Error for PathError {}
 
-    fn gt(&self, other: &Self) -> bool {
-        self.weight > other.weight    
+impl fmt::Display for SpanningTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpanningTreeError::Directed => write!(f, "spanning trees of directed graphs need a root, see `min_arborescence`"),
+            SpanningTreeError::Unreachable(v) => write!(f, "vertex {} is unreachable from the root", v),
+            SpanningTreeError::MissingEdge(u, v) => write!(f, "tree edge {} - {} is not in the graph", u, v),
+            SpanningTreeError::Cycle(u, v) => write!(f, "tree edge {} - {} closes a cycle", u, v),
+            SpanningTreeError::NotSpanning(u, v) => write!(f, "graph edge {} - {} joins two different trees", u, v),
+            SpanningTreeError::NotMinimum(u, v) => write!(f, "graph edge {} - {} is lighter than the tree path it closes", u, v),
+            SpanningTreeError::Overflow(u, v) => write!(f, "weights overflow along {} -> {}", u, v),
+        }
     }
+}
+
+impl std::error::Error for SpanningTreeError {}
 
-    fn le(&self, other: &Self) -> bool {
-        self.weight <= other.weight    
+impl<W: Weight> fmt::Display for WeightedGraph<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.edges.iter().enumerate().for_each(|(i, maybe_edge)| {
+            maybe_edge.as_ref().map(|edge| writeln!(f, "{}: {}", i, edge).expect("writer out of memory"));
+            ()
+        });
+        Ok(())
     }
+}
+    
 
-    fn ge(&self, other: &Self) -> bool {
-        self.weight >= other.weight    
+impl<W: Weight> PartialEq for EdgePair<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.weight.total_cmp(&other.weight) == Ordering::Equal && self.source == other.source && self.points_to == other.points_to    
     }
+}
 
+impl<W: Weight> PartialOrd for EdgePair<W> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self.weight <= other.weight, self.weight >= other.weight) {
-            (true, true) => {
-                Some(Ordering::Equal)
-            },
-            (false, true) => {
-                Some(Ordering::Greater)
-            },
-            (true, false) => {
-                Some(Ordering::Less)
-            },
-            _ => { panic!("impossible to have <= and >= both be false") }
-        }
+        Some(self.weight.total_cmp(&other.weight))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::graphs::test_utils::XorShift;
 
     #[test]
     fn prims() {
@@ -340,7 +932,7 @@ mod test {
         graph.insert_edge(4, 6, 5);
 
         graph.insert_edge(5, 6, 2);
-        (0..7).for_each(|start| assert_eq!(graph.prims(start).total_weight, 23));
+        (0..7).for_each(|start| assert_eq!(graph.prims(start).unwrap().total_weight(), 23));
     }
 
     #[test]
@@ -363,7 +955,7 @@ mod test {
         graph.insert_edge(4, 6, 5);
 
         graph.insert_edge(5, 6, 2);
-        assert_eq!(graph.kruskals().total_weight, 23);
+        assert_eq!(graph.kruskals().unwrap().total_weight(), 23);
     }
 
     #[test]
@@ -379,18 +971,445 @@ mod test {
         graph.insert_edge(2, 3, 1);
         graph.insert_edge(3, 4, 1);
 
-        let shortest_paths = graph.dijkstras(0);
+        let shortest_paths = graph.dijkstras(0).unwrap();
         assert_eq!(shortest_paths.path_to(4).unwrap().weight, 4);
         assert_eq!(shortest_paths.path_to(2).unwrap().weight, 2);
         assert_eq!(shortest_paths.path_to(1).unwrap().weight, 1);
 
-        let shortest_paths = graph.dijkstras(1);
+        let shortest_paths = graph.dijkstras(1).unwrap();
         assert_eq!(shortest_paths.path_to(4).unwrap().weight, 3);
 
-        let shortest_paths = graph.dijkstras(2);
+        let shortest_paths = graph.dijkstras(2).unwrap();
         assert_eq!(shortest_paths.path_to(4).unwrap().weight, 2);
  
-        let shortest_paths = graph.dijkstras(3);
+        let shortest_paths = graph.dijkstras(3).unwrap();
         assert_eq!(shortest_paths.path_to(4).unwrap().weight, 1);
     }
- }
+ 
+    // NOTE: the original O(V^2) Dijkstra, kept as a reference to check the heap-backed version
+    fn dijkstras_scan(graph: &WeightedGraph, start: usize) -> Vec<i32> {
+        let mut distance = vec![i32::MAX; graph.edges.len()];
+        let mut intree = vec![false; graph.edges.len()];
+        distance[start] = 0;
+        let mut cur_vertex = start;
+        while !intree[cur_vertex] {
+            intree[cur_vertex] = true;
+            let mut adj_v = &graph.edges[cur_vertex];
+            while let Some(edge) = adj_v.as_deref() {
+                if distance[edge.points_to] > distance[cur_vertex] + edge.weight { 
+                    distance[edge.points_to] = distance[cur_vertex] + edge.weight;
+                }
+                adj_v = &edge.next;
+            }
+            cur_vertex = match iter::zip(distance.iter(), intree.iter())
+                .enumerate()
+                .filter(|(_, (&d, &intree))| !intree && d != i32::MAX)
+                .min_by_key(|(_, (&d, _))| d) {
+                None => break,
+                Some(min) => min.0,
+            };
+        }
+        distance
+    }
+
+    #[test]
+    fn dijkstras_random() {
+        let mut rng = XorShift(0x2545f4914f6cdd1d);
+        for round in 0..50 {
+            let nvert = 2 + rng.below(40);
+            let nedges = rng.below(nvert * 4);
+            let graph = rng.weighted_graph(nvert, nedges, round % 2 == 0, 1..21);
+            let start = rng.below(nvert);
+            let expected = dijkstras_scan(&graph, start);
+            let shortest_paths = graph.dijkstras(start).unwrap();
+            assert_eq!(shortest_paths.distance, expected);
+            (0..nvert).filter(|&v| v != start).for_each(|v| {
+                match shortest_paths.path_to(v) {
+                    None => assert_eq!(expected[v], i32::MAX),
+                    Some(path) => {
+                        assert_eq!(path.weight, expected[v]);
+                        assert_eq!((path.path[0], *path.path.last().unwrap()), (start, v));
+                    },
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn bellman_ford() {
+        let mut graph = WeightedGraph::new(5, true);
+        graph.insert_edge(0, 1, 6);
+        graph.insert_edge(0, 3, 7);
+        graph.insert_edge(1, 2, 5);
+        graph.insert_edge(1, 3, 8);
+        graph.insert_edge(1, 4, -4);
+        graph.insert_edge(2, 1, -2);
+        graph.insert_edge(3, 2, -3);
+        graph.insert_edge(3, 4, 9);
+        graph.insert_edge(4, 0, 2);
+        graph.insert_edge(4, 2, 7);
+
+        let shortest_paths = graph.bellman_ford(0).unwrap();
+        assert_eq!(shortest_paths.distance, vec![0, 2, 4, 7, -2]);
+        let path = shortest_paths.path_to(4).unwrap();
+        assert_eq!(path.path, vec![0, 3, 2, 1, 4]);
+        assert_eq!(path.weight, -2);
+
+        assert_eq!(graph.dijkstras(0).unwrap_err(), PathError::NegativeEdge(1, 4));
+    }
+
+    #[test]
+    fn bellman_ford_negative_cycle() {
+        let mut graph = WeightedGraph::new(5, true);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(1, 2, 2);
+        graph.insert_edge(2, 3, -4);
+        graph.insert_edge(3, 1, 1);
+        graph.insert_edge(3, 4, 1);
+
+        let mut cycle = match graph.bellman_ford(0) {
+            Err(PathError::NegativeCycle(cycle)) => cycle,
+            other => panic!("expected a negative cycle, got {:?}", other),
+        };
+        // NOTE: rotate so the cycle starts at its smallest vertex
+        let min_idx = cycle.iter().enumerate().min_by_key(|(_, &v)| v).unwrap().0;
+        cycle.rotate_left(min_idx);
+        assert_eq!(cycle, vec![1, 2, 3]);
+
+        // NOTE: the cycle is not reachable from 4, so there is nothing to report
+        assert!(graph.bellman_ford(4).is_ok());
+
+        let mut graph = WeightedGraph::new(2, false);
+        graph.insert_edge(0, 1, -1);
+        assert!(matches!(graph.bellman_ford(0), Err(PathError::NegativeCycle(_))));
+    }
+
+    #[test]
+    fn bellman_ford_random() {
+        let mut rng = XorShift(0x9e3779b97f4a7c15);
+        for _ in 0..50 {
+            let nvert = 2 + rng.below(30);
+            let nedges = rng.below(nvert * 4);
+            let graph = rng.weighted_graph(nvert, nedges, true, 1..21);
+            let start = rng.below(nvert);
+            assert_eq!(graph.bellman_ford(start).unwrap().distance, graph.dijkstras(start).unwrap().distance);
+        }
+    }
+
+    fn manhattan(cols: usize, goal: usize, straight: i32) -> impl Fn(usize) -> i32 {
+        move |v| ((v / cols).abs_diff(goal / cols) + (v % cols).abs_diff(goal % cols)) as i32 * straight
+    }
+
+    fn octile(cols: usize, goal: usize, straight: i32, diagonal: i32) -> impl Fn(usize) -> i32 {
+        move |v| {
+            let (dr, dc) = ((v / cols).abs_diff(goal / cols) as i32, (v % cols).abs_diff(goal % cols) as i32);
+            straight * (dr + dc) + (diagonal - 2 * straight) * dr.min(dc)
+        }
+    }
+
+    #[test]
+    fn grid() {
+        let graph = WeightedGraph::grid(2, 3, 1, None, |_, _| true);
+        assert_eq!(graph.dijkstras(0).unwrap().distance, vec![0, 1, 2, 1, 2, 3]);
+        let graph = WeightedGraph::grid(2, 3, 10, Some(14), |r, c| (r, c) != (1, 1));
+        let shortest_paths = graph.dijkstras(0).unwrap();
+        assert_eq!(shortest_paths.distance, vec![0, 10, 20, 10, i32::MAX, 24]);
+    }
+
+    #[test]
+    fn a_star_manhattan() {
+        // NOTE: a wall down column 5 with a single gap in the last row
+        let (rows, cols) = (10, 10);
+        let graph = WeightedGraph::grid(rows, cols, 1, None, |r, c| c != 5 || r == rows - 1);
+        let (start, goal) = (0, cols - 1);
+        let (path, expanded) = graph.a_star(start, goal, manhattan(cols, goal, 1)).unwrap();
+        let path = path.unwrap();
+        assert_eq!(path.weight, graph.dijkstras(start).unwrap().distance[goal]);
+        assert_eq!(path.weight, 27);
+        assert_eq!((path.path[0], *path.path.last().unwrap()), (start, goal));
+        assert_eq!(path.path.len(), 28);
+
+        let (_, dijkstra_expanded) = graph.a_star(start, goal, |_| 0).unwrap();
+        assert!(expanded < dijkstra_expanded);
+
+        let (path, _) = graph.a_star(start, 5, manhattan(cols, 5, 1)).unwrap();
+        assert!(path.is_none());
+    }
+
+    #[test]
+    fn a_star_octile() {
+        let (rows, cols) = (20, 20);
+        let graph = WeightedGraph::grid(rows, cols, 10, Some(14), |r, c| !(r == 10 && c < 15));
+        for goal in [rows * cols - 1, 15 * cols + 3, 5] {
+            let (path, expanded) = graph.a_star(0, goal, octile(cols, goal, 10, 14)).unwrap();
+            let (_, dijkstra_expanded) = graph.a_star(0, goal, |_| 0).unwrap();
+            assert_eq!(path.unwrap().weight, graph.dijkstras(0).unwrap().distance[goal]);
+            assert!(expanded <= dijkstra_expanded);
+        }
+        let (path, expanded) = graph.a_star(0, 0, octile(cols, 0, 10, 14)).unwrap();
+        assert_eq!((path.unwrap().path, expanded), (vec![0], 0));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "heuristic is inconsistent")]
+    fn a_star_inconsistent() {
+        let graph = WeightedGraph::grid(3, 3, 1, None, |_, _| true);
+        let _ = graph.a_star(0, 8, |v| if v == 0 { 100 } else { 0 });
+    }
+
+    fn check_forest(graph: &WeightedGraph, tree: &MinSpanTree, expected_weight: i32, expected_components: usize) {
+        assert_eq!(tree.verify(graph), Ok(()));
+        assert_eq!(tree.total_weight(), expected_weight);
+        assert_eq!(tree.component_count(), expected_components);
+        assert_eq!(tree.edges().len(), graph.edges.len() - expected_components);
+        tree.edges().iter().for_each(|&(u, v, weight)| {
+            assert!(graph.incident(u).any(|edge| edge == (v, weight)));
+            assert_eq!(tree.component_of(u), tree.component_of(v));
+        });
+        (0..graph.edges.len()).for_each(|v| match tree.parent(v) {
+            None => assert!(tree.trees()[tree.component_of(v)].0[0] == v),
+            Some(p) => assert!(tree.edges().iter().any(|&(a, b, _)| (a, b) == (p, v) || (a, b) == (v, p))),
+        });
+    }
+
+    #[test]
+    fn spanning_forest() {
+        let mut graph = WeightedGraph::new(7, false);
+        graph.insert_edge(0, 1, 4);
+        graph.insert_edge(1, 2, 2);
+        graph.insert_edge(0, 2, 1);
+        graph.insert_edge(3, 4, 7);
+        graph.insert_edge(3, 4, 3);
+        graph.insert_edge(5, 5, 1);
+
+        let kruskals = graph.kruskals().unwrap();
+        check_forest(&graph, &kruskals, 6, 4);
+        (1..7).for_each(|start| check_forest(&graph, &graph.prims(start).unwrap(), 6, 4));
+        check_forest(&graph, &graph.boruvkas().unwrap(), 6, 4);
+        check_forest(&graph, &graph.reverse_delete().unwrap(), 6, 4);
+
+        let trees = kruskals.trees();
+        assert_eq!(trees[0], (vec![0, 1, 2], vec![(0, 2, 1), (1, 2, 2)]));
+        assert_eq!(trees[1], (vec![3, 4], vec![(3, 4, 3)]));
+        assert_eq!(trees[2], (vec![5], vec![]));
+        assert_eq!(trees[3], (vec![6], vec![]));
+    }
+
+    #[test]
+    fn spanning_forest_random() {
+        let mut rng = XorShift(0x1234abcd5678ef90);
+        for _ in 0..50 {
+            let nvert = 1 + rng.below(30);
+            let nedges = rng.below(nvert * 3);
+            let graph = rng.weighted_graph(nvert, nedges, false, -5..20);
+            let kruskals = graph.kruskals().unwrap();
+            let expected_components = kruskals.component_count();
+            check_forest(&graph, &kruskals, kruskals.total_weight(), expected_components);
+            let start = rng.below(nvert);
+            check_forest(&graph, &graph.prims(start).unwrap(), kruskals.total_weight(), expected_components);
+            check_forest(&graph, &graph.boruvkas().unwrap(), kruskals.total_weight(), expected_components);
+            check_forest(&graph, &graph.reverse_delete().unwrap(), kruskals.total_weight(), expected_components);
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let mut graph = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(1, 2, 2);
+        graph.insert_edge(2, 0, 3);
+        graph.insert_edge(2, 3, 1);
+        assert_eq!(graph.boruvkas().unwrap().edges().len(), 3);
+        assert_eq!(graph.reverse_delete().unwrap().total_weight(), 4);
+
+        let tree = MinSpanTree::new(&graph, vec![(0, 1, 1), (2, 0, 3), (2, 3, 1)]);
+        assert_eq!(tree.verify(&graph), Err(SpanningTreeError::NotMinimum(1, 2)));
+        let tree = MinSpanTree::new(&graph, vec![(0, 1, 1), (1, 2, 2)]);
+        assert_eq!(tree.verify(&graph), Err(SpanningTreeError::NotSpanning(2, 3)));
+        let tree = MinSpanTree::new(&graph, vec![(0, 1, 1), (1, 2, 2), (2, 0, 3), (2, 3, 1)]);
+        assert_eq!(tree.verify(&graph), Err(SpanningTreeError::Cycle(2, 0)));
+        let tree = MinSpanTree::new(&graph, vec![(0, 1, 1), (1, 3, 2), (2, 3, 1)]);
+        assert_eq!(tree.verify(&graph), Err(SpanningTreeError::MissingEdge(1, 3)));
+    }
+
+    #[test]
+    fn verify_random() {
+        let mut rng = XorShift(0x6a09e667f3bcc908);
+        for _ in 0..50 {
+            let nvert = 1 + rng.below(30);
+            let nedges = rng.below(nvert * 3);
+            let graph = rng.weighted_graph(nvert, nedges, false, -5..20);
+            let minimum = graph.kruskals().unwrap().total_weight();
+
+            // NOTE: some spanning forest, taking the edges in adjacency order instead of by weight
+            let mut set = union_find::UnionFind::new(nvert);
+            let mut edges = Vec::new();
+            (0..nvert).for_each(|u| graph.incident(u).for_each(|(v, weight)| {
+                if set.find(u) != set.find(v) {
+                    set.union(u, v);
+                    edges.push((u, v, weight));
+                }
+            }));
+            let forest = MinSpanTree::new(&graph, edges);
+            assert_eq!(forest.verify(&graph).is_ok(), forest.total_weight() == minimum);
+        }
+    }
+
+    #[test]
+    fn verify_long_path() {
+        let nvert = 200_000;
+        let mut graph = WeightedGraph::new(nvert, false);
+        (1..nvert).for_each(|v| graph.insert_edge(v - 1, v, 1));
+        graph.insert_edge(0, nvert - 1, 2);
+        graph.insert_edge(1, nvert - 2, 1);
+        let tree = graph.kruskals().unwrap();
+        assert_eq!(tree.verify(&graph), Ok(()));
+        let tree = MinSpanTree::new(&graph, (2..nvert).map(|v| (v - 1, v, 1)).chain([(0, nvert - 1, 2)]).collect());
+        assert_eq!(tree.verify(&graph), Err(SpanningTreeError::NotMinimum(0, 1)));
+    }
+
+    #[test]
+    fn spanning_tree_directed() {
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(1, 2, 1);
+        assert_eq!(graph.prims(0).unwrap_err(), SpanningTreeError::Directed);
+        assert_eq!(graph.kruskals().unwrap_err(), SpanningTreeError::Directed);
+        assert_eq!(graph.boruvkas().unwrap_err(), SpanningTreeError::Directed);
+        assert_eq!(graph.reverse_delete().unwrap_err(), SpanningTreeError::Directed);
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut graph: WeightedGraph<f64> = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 0.5);
+        graph.insert_edge(1, 2, 0.25);
+        graph.insert_edge(0, 2, 1.0);
+        let shortest_paths = graph.dijkstras(0).unwrap();
+        assert_eq!(shortest_paths.distance, vec![0.0, 0.5, 0.75, f64::INFINITY]);
+        assert_eq!(shortest_paths.path_to(2).unwrap().path, vec![0, 1, 2]);
+        assert_eq!(graph.bellman_ford(0).unwrap().distance, shortest_paths.distance);
+        assert_eq!(graph.kruskals().unwrap().total_weight(), 0.75);
+        assert_eq!(graph.prims(0).unwrap().total_weight(), 0.75);
+        assert_eq!(graph.prims(0).unwrap().verify(&graph), Ok(()));
+    }
+
+    #[test]
+    fn overflow() {
+        let mut graph = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX - 1);
+        graph.insert_edge(1, 2, 5);
+        assert_eq!(graph.dijkstras(0).unwrap_err(), PathError::Overflow(1, 2));
+        assert_eq!(graph.bellman_ford(0).unwrap_err(), PathError::Overflow(1, 2));
+        assert_eq!(graph.a_star(0, 2, |_| 0).unwrap_err(), PathError::Overflow(1, 2));
+        assert_eq!(graph.dijkstras(1).unwrap().distance_to(2), Some(5));
+
+        // NOTE: an overflowing detour does not matter once a shorter path is known
+        let mut graph = WeightedGraph::new(4, true);
+        graph.insert_edge(0, 1, i32::MAX - 1);
+        graph.insert_edge(1, 2, 5);
+        graph.insert_edge(0, 3, 20);
+        graph.insert_edge(3, 2, 5);
+        assert_eq!(graph.dijkstras(0).unwrap().distance_to(2), Some(25));
+        assert_eq!(graph.bellman_ford(0).unwrap().distance_to(2), Some(25));
+        assert_eq!(graph.a_star(0, 2, |_| 0).unwrap().0.unwrap().weight(), 25);
+
+        // NOTE: the negative cycle 0 -> 1 -> 0 drives the distances below `i32::MIN` on the second pass
+        let mut graph = WeightedGraph::new(2, true);
+        graph.insert_edge(0, 1, i32::MIN + 1);
+        graph.insert_edge(1, 0, 0);
+        assert_eq!(graph.bellman_ford(0).unwrap_err(), PathError::Overflow(0, 1));
+
+        let mut graph: WeightedGraph<i64> = WeightedGraph::new(3, true);
+        graph.insert_edge(0, 1, i32::MAX as i64);
+        graph.insert_edge(1, 2, 10);
+        assert_eq!(graph.dijkstras(0).unwrap().distance[2], i32::MAX as i64 + 10);
+    }
+
+    #[test]
+    fn shortest_paths_accessors() {
+        let mut graph = WeightedGraph::new(5, true);
+        graph.insert_edge(0, 1, 4);
+        graph.insert_edge(0, 1, 2);
+        graph.insert_edge(1, 2, 3);
+        graph.insert_edge(0, 2, 6);
+        graph.insert_edge(3, 0, 1);
+        let shortest_paths = graph.dijkstras(0).unwrap();
+        assert_eq!(shortest_paths.start(), 0);
+        assert_eq!(shortest_paths.distance_to(2), Some(5));
+        assert_eq!(shortest_paths.distance_to(3), None);
+        assert!(shortest_paths.is_reachable(1));
+        assert!(!shortest_paths.is_reachable(4));
+        assert_eq!(shortest_paths.iter_reachable().collect::<Vec<_>>(), vec![(0, 0), (1, 2), (2, 5)]);
+        assert_eq!(shortest_paths.parent(2), Some(1));
+        assert_eq!(format!("{}", shortest_paths), "0: [w:0]\n1: [w:2] via 0\n2: [w:5] via 1\n");
+
+        let tree = shortest_paths.shortest_path_tree();
+        assert_eq!(format!("{}", tree), "0: 1[w:2] \n1: 2[w:3] \n");
+        assert_eq!(tree.dijkstras(0).unwrap().distance, shortest_paths.distance);
+
+        let path = shortest_paths.path_to(2).unwrap();
+        assert_eq!(path.vertices(), &[0, 1, 2]);
+        assert_eq!(path.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2)]);
+        assert_eq!((path.weight(), path.len()), (5, 2));
+        assert_eq!(format!("{}", path), "0 -> 1 -> 2 [w:5]");
+
+        let path = shortest_paths.path_to(0).unwrap();
+        assert!(path.is_empty());
+        assert_eq!(format!("{}", path), "0 [w:0]");
+    }
+
+    #[test]
+    fn min_span_tree_display() {
+        let mut graph = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 3);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(0, 2, 5);
+        assert_eq!(format!("{}", graph.kruskals().unwrap()), "1 - 2[w:1]\n0 - 1[w:3]\ntotal[w:4] components: 2\n");
+    }
+
+    #[test]
+    fn counts() {
+        let mut graph = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 2);
+        graph.insert_edge(1, 2, 3);
+        graph.insert_edge(2, 2, 1);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (4, 3));
+        assert_eq!((0..4).map(|v| graph.degree(v)).collect::<Vec<_>>(), vec![1, 2, 3, 0]);
+
+        let mut directed = WeightedGraph::new(3, true);
+        directed.insert_edge(0, 1, 1);
+        directed.insert_edge(0, 2, 1);
+        directed.insert_edge(2, 1, 1);
+        assert_eq!((directed.out_degree(0), directed.in_degree(0), directed.degree(0)), (2, 0, 2));
+        assert_eq!((directed.out_degree(1), directed.in_degree(1), directed.degree(1)), (0, 2, 2));
+    }
+
+    #[test]
+    fn remove_edge() {
+        let mut graph = WeightedGraph::new(3, false);
+        graph.insert_edge(0, 1, 4);
+        graph.insert_edge(1, 0, 7);
+        // NOTE: the most recent parallel edge goes first, and only its own mirror entry with it
+        assert_eq!(graph.remove_edge(0, 1), Some(7));
+        assert_eq!(graph.incident(1).collect::<Vec<_>>(), vec![(0, 4)]);
+        assert_eq!(graph.remove_edge(1, 0), Some(4));
+        assert_eq!(graph.remove_edge(0, 1), None);
+        assert_eq!((graph.edge_count(), graph.degree(0), graph.degree(1)), (0, 0, 0));
+    }
+
+    #[test]
+    fn remove_vertex() {
+        let mut graph = WeightedGraph::new(4, false);
+        graph.insert_edge(0, 1, 1);
+        graph.insert_edge(1, 2, 1);
+        graph.insert_edge(0, 2, 5);
+        graph.insert_edge(2, 3, 1);
+        graph.remove_vertex(1);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (3, 2));
+        assert!(!graph.has_edge(0, 0) && graph.has_edge(0, 1) && graph.has_edge(2, 1));
+        let paths = graph.dijkstras(0).unwrap();
+        assert_eq!(paths.path_to(2).unwrap().weight(), 6);
+    }
+}